@@ -233,11 +233,11 @@ impl RebalSolArgs {
                 withdraw_to: wsol_withdraw_to,
                 lst_state_list: Keyed {
                     pubkey: lst_state_list_id,
-                    account: &spool.lst_state_list_account,
+                    account: &*spool.lst_state_list_account,
                 },
                 pool_state: Keyed {
                     pubkey: pool_id,
-                    account: &spool.pool_state_account.unwrap(),
+                    account: spool.pool_state_account.as_deref().unwrap(),
                 },
                 src_lst_mint: MintWithTokenProgram {
                     pubkey: native_mint::ID,
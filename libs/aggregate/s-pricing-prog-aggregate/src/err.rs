@@ -7,6 +7,7 @@ use std::{
 pub enum PricingProgErr {
     UnknownPricingProg,
     WrongPricingProg,
+    PricingPaused,
 }
 
 impl Display for PricingProgErr {
@@ -14,6 +15,7 @@ impl Display for PricingProgErr {
         match self {
             Self::UnknownPricingProg => f.write_str("Unknown pricing program"),
             Self::WrongPricingProg => f.write_str("Wrong pricing program"),
+            Self::PricingPaused => f.write_str("Pricing paused"),
         }
     }
 }
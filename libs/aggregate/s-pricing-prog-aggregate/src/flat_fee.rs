@@ -1,12 +1,11 @@
 use flat_fee_interface::{
-    FeeAccount, FlatFeeError, PriceLpTokensToMintKeys, ProgramState,
-    PRICE_EXACT_IN_IX_ACCOUNTS_LEN, PRICE_EXACT_OUT_IX_ACCOUNTS_LEN,
-    PRICE_LP_TOKENS_TO_MINT_IX_ACCOUNTS_LEN, PRICE_LP_TOKENS_TO_REDEEM_IX_ACCOUNTS_LEN,
+    FeeAccount, FlatFeeError, ProgramState, PRICE_EXACT_IN_IX_ACCOUNTS_LEN,
+    PRICE_EXACT_OUT_IX_ACCOUNTS_LEN, PRICE_LP_TOKENS_TO_REDEEM_IX_ACCOUNTS_LEN,
 };
 use flat_fee_lib::{
     account_resolvers::{
         PriceExactInFreeArgs, PriceExactInWithBumpFreeArgs, PriceExactOutFreeArgs,
-        PriceExactOutWithBumpFreeArgs, PriceLpTokensToRedeemFreeArgs,
+        PriceExactOutWithBumpFreeArgs, PriceLpTokensToMintFreeArgs, PriceLpTokensToRedeemFreeArgs,
     },
     calc::{
         calculate_price_exact_in, calculate_price_exact_out, calculate_price_lp_tokens_to_redeem,
@@ -15,6 +14,7 @@ use flat_fee_lib::{
     pda::{FeeAccountCreatePdaArgs, FeeAccountFindPdaArgs, ProgramStateFindPdaArgs},
     utils::{try_fee_account, try_program_state},
 };
+use sanctum_token_ratio::BPS_DENOMINATOR;
 use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
 use solana_readonly_account::ReadonlyAccountData;
 use std::collections::HashMap;
@@ -29,6 +29,11 @@ pub struct FlatFeePricingProg {
 }
 
 impl FlatFeePricingProg {
+    /// Mints of the LSTs this was created for
+    pub fn lst_mints(&self) -> impl Iterator<Item = &Pubkey> {
+        self.mints_to_fee_accounts.keys()
+    }
+
     pub fn find_program_state_addr(&self) -> Pubkey {
         ProgramStateFindPdaArgs {
             program_id: self.program_id,
@@ -37,6 +42,25 @@ impl FlatFeePricingProg {
         .0
     }
 
+    /// Returns the program state and fee accounts that have not been fetched yet
+    pub fn missing_accounts(&self) -> Vec<Pubkey> {
+        let program_state = self
+            .program_state
+            .is_none()
+            .then(|| self.find_program_state_addr());
+        program_state
+            .into_iter()
+            .chain(
+                self.mints_to_fee_accounts
+                    .iter()
+                    .filter(|(_lst_mint, fee_account_opt)| fee_account_opt.is_none())
+                    .map(|(lst_mint, fee_account_opt)| {
+                        self.fee_account_for_mint(lst_mint, fee_account_opt)
+                    }),
+            )
+            .collect()
+    }
+
     fn get_fee_account_checked(&self, lst_mint: &Pubkey) -> Result<&FeeAccount, FlatFeeError> {
         match self.mints_to_fee_accounts.get(lst_mint) {
             Some(Some(a)) => Ok(a),
@@ -91,6 +115,16 @@ impl FlatFeePricingProg {
     }
 }
 
+/// The flat fee program has no pause flag, so pricing for a pair is considered paused
+/// if its combined fees are >= 100%, since every quote for it would be 0 or fail
+fn check_not_paused(input_fee_bps: i16, output_fee_bps: i16) -> Result<(), PricingProgErr> {
+    if i32::from(input_fee_bps) + i32::from(output_fee_bps) >= i32::from(BPS_DENOMINATOR) {
+        Err(PricingProgErr::PricingPaused)
+    } else {
+        Ok(())
+    }
+}
+
 impl MutablePricingProg for FlatFeePricingProg {
     fn try_new<I: Iterator<Item = Pubkey>>(
         program_id: Pubkey,
@@ -207,12 +241,9 @@ impl PricingProg for FlatFeePricingProg {
         &self,
         input_lst_mint: Pubkey,
     ) -> anyhow::Result<Vec<AccountMeta>> {
-        Ok(
-            <[AccountMeta; PRICE_LP_TOKENS_TO_MINT_IX_ACCOUNTS_LEN]>::from(
-                PriceLpTokensToMintKeys { input_lst_mint },
-            )
-            .into(),
-        )
+        Ok(PriceLpTokensToMintFreeArgs { input_lst_mint }
+            .resolve_to_account_metas()
+            .into())
     }
 
     fn quote_exact_in(
@@ -225,6 +256,7 @@ impl PricingProg for FlatFeePricingProg {
     ) -> anyhow::Result<u64> {
         let FeeAccount { input_fee_bps, .. } = self.get_fee_account_checked(&input_lst_mint)?;
         let FeeAccount { output_fee_bps, .. } = self.get_fee_account_checked(&output_lst_mint)?;
+        check_not_paused(*input_fee_bps, *output_fee_bps)?;
         Ok(calculate_price_exact_in(CalculatePriceExactInArgs {
             input_fee_bps: *input_fee_bps,
             output_fee_bps: *output_fee_bps,
@@ -265,6 +297,7 @@ impl PricingProg for FlatFeePricingProg {
     ) -> anyhow::Result<u64> {
         let FeeAccount { input_fee_bps, .. } = self.get_fee_account_checked(&input_lst_mint)?;
         let FeeAccount { output_fee_bps, .. } = self.get_fee_account_checked(&output_lst_mint)?;
+        check_not_paused(*input_fee_bps, *output_fee_bps)?;
         Ok(calculate_price_exact_out(CalculatePriceExactOutArgs {
             input_fee_bps: *input_fee_bps,
             output_fee_bps: *output_fee_bps,
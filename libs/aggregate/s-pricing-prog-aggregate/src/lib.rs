@@ -4,7 +4,7 @@ use pricing_programs_interface::{
 };
 use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
 use solana_readonly_account::ReadonlyAccountData;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 mod err;
 mod flat_fee;
@@ -19,6 +19,45 @@ pub enum KnownPricingProg {
     FlatFee(FlatFeePricingProg), // only variant for now
 }
 
+/// Fieldless discriminant of [`KnownPricingProg`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PricingProgKind {
+    FlatFee,
+}
+
+impl PricingProgKind {
+    /// Kind of the pricing program with program ID `program_id`
+    pub fn try_from_program_id(program_id: Pubkey) -> Result<Self, PricingProgErr> {
+        match program_id {
+            flat_fee_lib::program::ID => Ok(Self::FlatFee),
+            _ => Err(PricingProgErr::UnknownPricingProg),
+        }
+    }
+}
+
+impl KnownPricingProg {
+    pub fn kind(&self) -> PricingProgKind {
+        match self {
+            Self::FlatFee(_) => PricingProgKind::FlatFee,
+        }
+    }
+
+    /// Mints of the LSTs this was created for with [`MutablePricingProg::try_new`]
+    pub fn lst_mints(&self) -> HashSet<Pubkey> {
+        match self {
+            Self::FlatFee(p) => p.lst_mints().copied().collect(),
+        }
+    }
+
+    /// Returns the pricing program accounts that must still be fetched and passed to `update()`
+    /// before this can price every LST it was created for
+    pub fn missing_accounts(&self) -> Vec<Pubkey> {
+        match self {
+            Self::FlatFee(p) => p.missing_accounts(),
+        }
+    }
+}
+
 impl MutablePricingProg for KnownPricingProg {
     fn try_new<I: Iterator<Item = Pubkey>>(
         program_id: Pubkey,
@@ -72,6 +72,22 @@ pub trait MutablePricingProg {
         &mut self,
         account_map: &HashMap<Pubkey, D>,
     ) -> anyhow::Result<()>;
+
+    /// Applies a single fetched account, e.g. from a geyser account update.
+    ///
+    /// Returns whether `key` is one of [`Self::get_accounts_to_update`].
+    /// The account is ignored if it is not.
+    fn update_account<D: ReadonlyAccountData>(
+        &mut self,
+        key: Pubkey,
+        account: D,
+    ) -> anyhow::Result<bool> {
+        if !self.get_accounts_to_update().contains(&key) {
+            return Ok(false);
+        }
+        self.update(&HashMap::from([(key, account)]))?;
+        Ok(true)
+    }
 }
 
 pub trait PricingProg {
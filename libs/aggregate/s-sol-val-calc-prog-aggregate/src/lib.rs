@@ -1,7 +1,7 @@
 // TODO: all generic pool calculator implementations currently assume the stake pool program is never updated,
 // otherwise, get_accounts_to_update() will include the very large programdata accounts.
 
-use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+use solana_program::{clock::Clock, instruction::AccountMeta, pubkey::Pubkey};
 use solana_readonly_account::ReadonlyAccountData;
 use std::collections::HashMap;
 
@@ -33,6 +33,86 @@ pub enum KnownLstSolValCalc {
     SanctumSplMulti(SanctumSplMultiLstSolValCalc),
 }
 
+/// Fieldless discriminant of [`KnownLstSolValCalc`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LstSolValCalcKind {
+    Lido,
+    Marinade,
+    Spl,
+    SanctumSpl,
+    Wsol,
+    SanctumSplMulti,
+}
+
+impl KnownLstSolValCalc {
+    pub fn kind(&self) -> LstSolValCalcKind {
+        match self {
+            Self::Lido(_) => LstSolValCalcKind::Lido,
+            Self::Marinade(_) => LstSolValCalcKind::Marinade,
+            Self::Spl(_) => LstSolValCalcKind::Spl,
+            Self::SanctumSpl(_) => LstSolValCalcKind::SanctumSpl,
+            Self::Wsol(_) => LstSolValCalcKind::Wsol,
+            Self::SanctumSplMulti(_) => LstSolValCalcKind::SanctumSplMulti,
+        }
+    }
+
+    /// Overwrites the clock consulted by calculators whose sol value depends on the current epoch.
+    /// No-op for calculators that don't use the clock.
+    ///
+    /// The clock is overwritten again by the next `update()` with the clock sysvar in its account map.
+    pub fn set_clock(&mut self, clock: Clock) {
+        match self {
+            Self::Lido(s) => s.clock = Some(clock),
+            Self::Spl(s) => s.clock = Some(clock),
+            Self::SanctumSpl(s) => s.0.clock = Some(clock),
+            Self::SanctumSplMulti(s) => s.0.clock = Some(clock),
+            Self::Marinade(_) | Self::Wsol(_) => (),
+        }
+    }
+
+    /// Returns the accounts that must still be fetched and passed to `update()`
+    /// before this can calculate sol values, empty if none.
+    ///
+    /// All of [`MutableLstSolValCalc::get_accounts_to_update`] is returned if either
+    /// the calculator state or, for calculators that check the current epoch, the clock is missing.
+    pub fn missing_accounts(&self) -> Vec<Pubkey> {
+        let is_clock_fetched = match self {
+            Self::Lido(s) => s.clock.is_some(),
+            Self::Spl(s) => s.clock.is_some(),
+            Self::SanctumSpl(s) => s.0.clock.is_some(),
+            Self::SanctumSplMulti(s) => s.0.clock.is_some(),
+            Self::Marinade(_) | Self::Wsol(_) => true,
+        };
+        if is_clock_fetched && self.sol_value_calculator().is_some() {
+            vec![]
+        } else {
+            self.get_accounts_to_update()
+        }
+    }
+
+    /// Creates the calculator of an SPL stake pool LST from its stake pool address alone,
+    /// for LSTs that are not on the sanctum LST list.
+    ///
+    /// Returns `None` if `sol_value_calculator` is not one of the SPL sol value calculator programs.
+    pub fn try_from_spl_calc_program(
+        sol_value_calculator: Pubkey,
+        keys: SplLstSolValCalcInitKeys,
+    ) -> Option<Self> {
+        let calc = if sol_value_calculator == spl_calculator_lib::program::ID {
+            Self::Spl(SplLstSolValCalc::from_keys(keys))
+        } else if sol_value_calculator == spl_calculator_lib::sanctum_spl_sol_val_calc_program::ID {
+            Self::SanctumSpl(SanctumSplLstSolValCalc::from_keys(keys))
+        } else if sol_value_calculator
+            == spl_calculator_lib::sanctum_spl_multi_sol_val_calc_program::ID
+        {
+            Self::SanctumSplMulti(SanctumSplMultiLstSolValCalc::from_keys(keys))
+        } else {
+            return None;
+        };
+        Some(calc)
+    }
+}
+
 impl MutableLstSolValCalc for KnownLstSolValCalc {
     fn get_accounts_to_update(&self) -> Vec<Pubkey> {
         match self {
@@ -2,6 +2,7 @@ mod add_lst;
 mod initialize;
 mod price_exact_in;
 mod price_exact_out;
+mod price_lp_tokens_to_mint;
 mod price_lp_tokens_to_redeem;
 mod remove_lst;
 mod set_lp_withdrawal_fee;
@@ -12,6 +13,7 @@ pub use add_lst::*;
 pub use initialize::*;
 pub use price_exact_in::*;
 pub use price_exact_out::*;
+pub use price_lp_tokens_to_mint::*;
 pub use price_lp_tokens_to_redeem::*;
 pub use remove_lst::*;
 pub use set_lp_withdrawal_fee::*;
@@ -1,9 +1,11 @@
-use s_controller_interface::DisablePoolKeys;
+use s_controller_interface::{DisablePoolKeys, SControllerError};
 use solana_program::pubkey::Pubkey;
+use solana_readonly_account::{ReadonlyAccountData, ReadonlyAccountPubkey};
 
 use crate::{
     find_disable_pool_authority_list_address, find_pool_state_address,
     program::{DISABLE_POOL_AUTHORITY_LIST_ID, POOL_STATE_ID},
+    try_pool_state,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -34,3 +36,36 @@ impl DisablePoolFreeArgs {
         }
     }
 }
+
+/// [`DisablePoolFreeArgs`] with the pool's current admin as signer.
+/// Use [`DisablePoolFreeArgs`] directly to sign with a disable pool authority instead.
+#[derive(Clone, Copy, Debug)]
+pub struct DisablePoolByAdminFreeArgs<S> {
+    pub pool_state_acc: S,
+}
+
+impl<S: ReadonlyAccountData + ReadonlyAccountPubkey> DisablePoolByAdminFreeArgs<S> {
+    pub fn resolve(&self) -> Result<DisablePoolKeys, SControllerError> {
+        if *self.pool_state_acc.pubkey() != POOL_STATE_ID {
+            return Err(SControllerError::IncorrectPoolState);
+        }
+        Ok(self.free_args()?.resolve())
+    }
+}
+
+impl<S: ReadonlyAccountData> DisablePoolByAdminFreeArgs<S> {
+    pub fn resolve_for_prog(
+        &self,
+        program_id: Pubkey,
+    ) -> Result<DisablePoolKeys, SControllerError> {
+        Ok(self.free_args()?.resolve_for_prog(program_id))
+    }
+
+    fn free_args(&self) -> Result<DisablePoolFreeArgs, SControllerError> {
+        let pool_state_data = self.pool_state_acc.data();
+        let pool_state = try_pool_state(&pool_state_data)?;
+        Ok(DisablePoolFreeArgs {
+            signer: pool_state.admin,
+        })
+    }
+}
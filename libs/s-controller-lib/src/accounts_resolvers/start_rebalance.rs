@@ -148,6 +148,7 @@ impl<
         self.resolve_with_pdas(RebalancePdas::find_for_program_id(program_id))
     }
 
+    /// Errors with `SwapSameLst` if src and dst LST mints are the same
     pub fn resolve_with_pdas(
         self,
         RebalancePdas {
@@ -163,6 +164,9 @@ impl<
         ),
         SControllerError,
     > {
+        if self.src_lst_mint.pubkey() == self.dst_lst_mint.pubkey() {
+            return Err(SControllerError::SwapSameLst);
+        }
         let lst_state_list_acc_data = self.lst_state_list.data();
         let list = try_lst_state_list(&lst_state_list_acc_data)?;
 
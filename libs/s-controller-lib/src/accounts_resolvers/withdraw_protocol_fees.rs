@@ -4,10 +4,11 @@ use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 use solana_readonly_account::{ReadonlyAccountData, ReadonlyAccountOwner, ReadonlyAccountPubkey};
 
 use crate::{
-    find_pool_state_address, find_protocol_fee_accumulator_address,
+    create_protocol_fee_accumulator_address_with_protocol_fee_id, find_pool_state_address,
+    find_protocol_fee_accumulator_address,
     find_protocol_fee_accumulator_address_with_protocol_fee_id, find_protocol_fee_address,
     program::{POOL_STATE_ID, PROTOCOL_FEE_ID},
-    try_pool_state, FindLstPdaAtaKeys,
+    try_find_lst_mint_on_list, try_lst_state_list, try_pool_state, FindLstPdaAtaKeys,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -118,3 +119,73 @@ impl<S: ReadonlyAccountData, M: ReadonlyAccountOwner + ReadonlyAccountPubkey>
         })
     }
 }
+
+/// Iterates through lst_state_list to create the protocol fee accumulator address
+/// from the LST's `protocol_fee_accumulator_bump`.
+/// Suitable for use on client-side.
+/// Does not check identity of pool_state and lst_state_list
+#[derive(Clone, Copy, Debug)]
+pub struct WithdrawProtocolFeesByListedMintFreeArgs<S, L, M> {
+    pub pool_state: S,
+    pub lst_state_list: L,
+    pub lst_mint: M,
+    pub withdraw_to: Pubkey,
+}
+
+impl<
+        S: ReadonlyAccountData,
+        L: ReadonlyAccountData,
+        M: ReadonlyAccountOwner + ReadonlyAccountPubkey,
+    > WithdrawProtocolFeesByListedMintFreeArgs<S, L, M>
+{
+    /// Errors with `InvalidLstIndex` if lst_mint is not on lst_state_list
+    pub fn resolve(self) -> Result<WithdrawProtocolFeesKeys, ProgramError> {
+        self.resolve_with_pool_state_and_protocol_fee(POOL_STATE_ID, PROTOCOL_FEE_ID)
+    }
+
+    /// Errors with `InvalidLstIndex` if lst_mint is not on lst_state_list
+    pub fn resolve_for_prog(
+        self,
+        program_id: Pubkey,
+    ) -> Result<WithdrawProtocolFeesKeys, ProgramError> {
+        self.resolve_with_pool_state_and_protocol_fee(
+            find_pool_state_address(program_id).0,
+            find_protocol_fee_address(program_id).0,
+        )
+    }
+
+    fn resolve_with_pool_state_and_protocol_fee(
+        self,
+        pool_state: Pubkey,
+        protocol_fee_accumulator_auth: Pubkey,
+    ) -> Result<WithdrawProtocolFeesKeys, ProgramError> {
+        let Self {
+            pool_state: pool_state_acc,
+            lst_state_list,
+            lst_mint,
+            withdraw_to,
+        } = self;
+
+        let protocol_fee_accumulator = {
+            let lst_state_list_acc_data = lst_state_list.data();
+            let list = try_lst_state_list(&lst_state_list_acc_data)?;
+            let (_lst_index, lst_state) = try_find_lst_mint_on_list(*lst_mint.pubkey(), list)?;
+            create_protocol_fee_accumulator_address_with_protocol_fee_id(
+                protocol_fee_accumulator_auth,
+                lst_state,
+                *lst_mint.owner(),
+            )?
+        };
+
+        WithdrawProtocolFeesByMintFreeArgs {
+            pool_state: pool_state_acc,
+            lst_mint,
+            withdraw_to,
+        }
+        .resolve_with_pdas(WithdrawProtocolFeesPdas {
+            pool_state,
+            protocol_fee_accumulator_auth,
+            protocol_fee_accumulator,
+        })
+    }
+}
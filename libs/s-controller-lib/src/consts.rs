@@ -1,5 +1,11 @@
 use solana_program::pubkey::Pubkey;
 
+/// 1-byte discriminants that instruction data of the pool's user-facing instructions start with
+pub use s_controller_interface::{
+    ADD_LIQUIDITY_IX_DISCM, REMOVE_LIQUIDITY_IX_DISCM, SWAP_EXACT_IN_IX_DISCM,
+    SWAP_EXACT_OUT_IX_DISCM, SYNC_SOL_VALUE_IX_DISCM,
+};
+
 pub mod initial_authority {
     #[cfg(feature = "testing")]
     sanctum_macros::declare_program_keys!("9S3avfRxH9RYbMHbvxnhwiwpdF9iuXG7uWiatqWvQskT", []);
@@ -5,20 +5,24 @@ mod add_liquidity;
 mod disable_enable_lst_input;
 mod end_rebalance;
 mod remove_liquidity;
+mod set_protocol_fee;
 mod set_sol_value_calculator;
 mod start_rebalance;
 mod swap_exact_in;
 mod swap_exact_out;
 mod sync_sol_value;
 mod utils;
+mod withdraw_protocol_fees;
 
 pub use add_liquidity::*;
 pub use disable_enable_lst_input::*;
 pub use end_rebalance::*;
 pub use remove_liquidity::*;
+pub use set_protocol_fee::*;
 pub use set_sol_value_calculator::*;
 pub use start_rebalance::*;
 pub use swap_exact_in::*;
 pub use swap_exact_out::*;
 pub use sync_sol_value::*;
 pub use utils::*;
+pub use withdraw_protocol_fees::*;
@@ -0,0 +1,47 @@
+use s_controller_interface::{
+    set_protocol_fee_ix, set_protocol_fee_ix_with_program_id, SControllerError,
+    SetProtocolFeeIxArgs,
+};
+use sanctum_token_ratio::BPS_DENOMINATOR;
+use solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+use solana_readonly_account::{ReadonlyAccountData, ReadonlyAccountPubkey};
+
+use crate::SetProtocolFeeFreeArgs;
+
+/// Errors if any of the new fees set exceed 100%
+pub fn verify_protocol_fee_bps(
+    SetProtocolFeeIxArgs {
+        new_trading_protocol_fee_bps,
+        new_lp_protocol_fee_bps,
+    }: &SetProtocolFeeIxArgs,
+) -> Result<(), SControllerError> {
+    if [new_trading_protocol_fee_bps, new_lp_protocol_fee_bps]
+        .into_iter()
+        .flatten()
+        .any(|fee_bps| *fee_bps > BPS_DENOMINATOR)
+    {
+        return Err(SControllerError::FeeTooHigh);
+    }
+    Ok(())
+}
+
+pub fn set_protocol_fee_ix_full<S: ReadonlyAccountData + ReadonlyAccountPubkey>(
+    free_args: SetProtocolFeeFreeArgs<S>,
+    args: SetProtocolFeeIxArgs,
+) -> Result<Instruction, ProgramError> {
+    verify_protocol_fee_bps(&args)?;
+    let keys = free_args.resolve()?;
+    let ix = set_protocol_fee_ix(keys, args)?;
+    Ok(ix)
+}
+
+pub fn set_protocol_fee_ix_full_for_prog<S: ReadonlyAccountData>(
+    program_id: Pubkey,
+    free_args: SetProtocolFeeFreeArgs<S>,
+    args: SetProtocolFeeIxArgs,
+) -> Result<Instruction, ProgramError> {
+    verify_protocol_fee_bps(&args)?;
+    let keys = free_args.resolve_for_prog(program_id)?;
+    let ix = set_protocol_fee_ix_with_program_id(program_id, keys, args)?;
+    Ok(ix)
+}
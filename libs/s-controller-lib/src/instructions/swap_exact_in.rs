@@ -1,6 +1,5 @@
 use s_controller_interface::{
-    swap_exact_in_ix_with_program_id, SControllerError, SwapExactInIxArgs, SwapExactInIxData,
-    SwapExactInKeys,
+    swap_exact_in_ix_with_program_id, SControllerError, SwapExactInIxArgs, SwapExactInKeys,
 };
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -56,23 +55,25 @@ pub fn swap_exact_in_ix_full_for_prog(
 ) -> Result<Instruction, ProgramError> {
     let src_lst_index = index_to_u32(src_lst_index)?;
     let dst_lst_index = index_to_u32(dst_lst_index)?;
+    let SrcDstLstSolValueCalcExtendCount {
+        src_lst: src_lst_value_calc_accs,
+        dst_lst: dst_lst_value_calc_accs,
+    } = sol_val_calc_accounts
+        .extend_count()
+        .map_err(|_e| SControllerError::MathError)?;
     let mut ix = swap_exact_in_ix_with_program_id(
         program_id,
         accounts,
         SwapExactInIxArgs {
-            // zeroes replaced by ix_extend below
-            src_lst_value_calc_accs: 0,
-            dst_lst_value_calc_accs: 0,
+            src_lst_value_calc_accs,
+            dst_lst_value_calc_accs,
             src_lst_index,
             dst_lst_index,
             min_amount_out,
             amount,
         },
     )?;
-    let SrcDstLstSolValueCalcExtendCount {
-        src_lst: src_lst_value_calc_accs,
-        dst_lst: dst_lst_value_calc_accs,
-    } = ix_extend_with_src_dst_sol_value_calculator_accounts(&mut ix, sol_val_calc_accounts)
+    ix_extend_with_src_dst_sol_value_calculator_accounts(&mut ix, sol_val_calc_accounts)
         .map_err(|_e| SControllerError::MathError)?;
     ix_extend_with_pricing_program_price_swap_accounts(
         &mut ix,
@@ -80,17 +81,6 @@ pub fn swap_exact_in_ix_full_for_prog(
         pricing_program_id,
     )
     .map_err(|_e| SControllerError::MathError)?;
-    // TODO: better way to update *_calc_accs than double serialization here
-    let mut overwrite = &mut ix.data[..];
-    SwapExactInIxData(SwapExactInIxArgs {
-        src_lst_value_calc_accs,
-        dst_lst_value_calc_accs,
-        src_lst_index,
-        dst_lst_index,
-        min_amount_out,
-        amount,
-    })
-    .serialize(&mut overwrite)?;
     Ok(ix)
 }
 
@@ -48,6 +48,16 @@ impl<'me> SrcDstLstSolValueCalcAccounts<'me> {
             dst_lst_calculator_accounts,
         }
     }
+
+    /// The counts [`ix_extend_with_src_dst_sol_value_calculator_accounts`] returns,
+    /// computed without extending an instruction
+    pub fn extend_count(&self) -> Result<SrcDstLstSolValueCalcExtendCount, TryFromIntError> {
+        // n_accounts = len() - 1 (mint) + 1 (program ID)
+        Ok(SrcDstLstSolValueCalcExtendCount {
+            src_lst: self.src_lst_calculator_accounts.len().try_into()?,
+            dst_lst: self.dst_lst_calculator_accounts.len().try_into()?,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
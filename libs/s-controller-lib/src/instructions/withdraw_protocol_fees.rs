@@ -0,0 +1,44 @@
+use s_controller_interface::{
+    withdraw_protocol_fees_ix_with_program_id, WithdrawProtocolFeesIxArgs, WithdrawProtocolFeesKeys,
+};
+use sanctum_token_lib::token_account_balance;
+use solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+use solana_readonly_account::{ReadonlyAccountData, ReadonlyAccountPubkey};
+
+/// `amount: None` withdraws the entire balance of `protocol_fee_accumulator`,
+/// which must be the token account at `keys.protocol_fee_accumulator`
+pub fn withdraw_protocol_fees_ix_full<A: ReadonlyAccountData + ReadonlyAccountPubkey>(
+    keys: WithdrawProtocolFeesKeys,
+    amount: Option<u64>,
+    protocol_fee_accumulator: A,
+) -> Result<Instruction, ProgramError> {
+    withdraw_protocol_fees_ix_full_for_prog(
+        crate::program::ID,
+        keys,
+        amount,
+        protocol_fee_accumulator,
+    )
+}
+
+pub fn withdraw_protocol_fees_ix_full_for_prog<A: ReadonlyAccountData + ReadonlyAccountPubkey>(
+    program_id: Pubkey,
+    keys: WithdrawProtocolFeesKeys,
+    amount: Option<u64>,
+    protocol_fee_accumulator: A,
+) -> Result<Instruction, ProgramError> {
+    let amount = match amount {
+        Some(amount) => amount,
+        None => {
+            if *protocol_fee_accumulator.pubkey() != keys.protocol_fee_accumulator {
+                return Err(ProgramError::InvalidArgument);
+            }
+            token_account_balance(protocol_fee_accumulator)?
+        }
+    };
+    let ix = withdraw_protocol_fees_ix_with_program_id(
+        program_id,
+        keys,
+        WithdrawProtocolFeesIxArgs { amount },
+    )?;
+    Ok(ix)
+}
@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+
+use jupiter_amm_interface::{SwapAndAccountMetas, SwapParams};
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::instruction::AccountMeta;
+
+use crate::{SPool, SPoolError};
+
+/// Max number of unique accounts a transaction can lock
+pub const MAX_TX_ACCOUNTS: usize = 64;
+
+/// Errors with [`SPoolError::TooManyAccounts`] if the unique accounts of `account_metas`
+/// plus `other_accounts` accounts used by the rest of the transaction exceed [`MAX_TX_ACCOUNTS`].
+///
+/// `other_accounts` is assumed to not overlap with `account_metas`.
+pub fn check_tx_account_limit(
+    account_metas: &[AccountMeta],
+    other_accounts: usize,
+) -> Result<(), SPoolError> {
+    let unique: HashSet<_> = account_metas.iter().map(|meta| meta.pubkey).collect();
+    let accounts = unique.len().saturating_add(other_accounts);
+    if accounts > MAX_TX_ACCOUNTS {
+        return Err(SPoolError::TooManyAccounts {
+            accounts,
+            max: MAX_TX_ACCOUNTS,
+        });
+    }
+    Ok(())
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// [`Self::get_swap_and_account_metas_full`], but errors with [`SPoolError::TooManyAccounts`]
+    /// instead of returning metas that would not fit in a transaction
+    /// that already uses `other_accounts` other accounts.
+    pub fn get_swap_and_account_metas_within_limit(
+        &self,
+        swap_params: &SwapParams,
+        other_accounts: usize,
+    ) -> Result<SwapAndAccountMetas, SPoolError> {
+        let res = self.get_swap_and_account_metas_full(swap_params)?;
+        check_tx_account_limit(&res.account_metas, other_accounts)?;
+        Ok(res)
+    }
+}
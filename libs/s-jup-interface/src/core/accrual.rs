@@ -0,0 +1,77 @@
+use anyhow::anyhow;
+use jupiter_amm_interface::{Quote, QuoteParams};
+use rust_decimal::Decimal;
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_token_ratio::BPS_DENOMINATOR;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{SPool, SPoolError};
+
+/// Running totals of swaps passed to [`SPool::record_swap`], in lamports of SOL value
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapAccrual {
+    pub volume_sol_value: u128,
+    pub fees_sol_value: u128,
+}
+
+impl<S, L> SPool<S, L> {
+    /// Fees as a fraction of volume of all swaps passed to [`Self::record_swap`], in bps.
+    ///
+    /// `None` if no volume has been recorded.
+    pub fn realized_fee_bps(&self) -> Option<Decimal> {
+        let SwapAccrual {
+            volume_sol_value,
+            fees_sol_value,
+        } = self.swap_accrual;
+        if volume_sol_value == 0 {
+            return None;
+        }
+        let fees = Decimal::try_from_i128_with_scale(fees_sol_value.try_into().ok()?, 0).ok()?;
+        let volume =
+            Decimal::try_from_i128_with_scale(volume_sol_value.try_into().ok()?, 0).ok()?;
+        fees.checked_mul(Decimal::from(BPS_DENOMINATOR))?
+            .checked_div(volume)
+    }
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Adds the SOL values of a swap's input amount and fee to [`SPool::swap_accrual`],
+    /// valued with the current sol value calculators.
+    /// Swaps of LSTs excluded by [`Self::set_allowlist`] since they were quoted can still be recorded.
+    ///
+    /// Only LST -> LST swaps are supported.
+    pub fn record_swap(
+        &mut self,
+        QuoteParams { input_mint, .. }: &QuoteParams,
+        Quote {
+            in_amount,
+            fee_amount,
+            fee_mint,
+            ..
+        }: &Quote,
+    ) -> anyhow::Result<()> {
+        let volume = self.lst_sol_value(*input_mint, *in_amount)?;
+        let fees = self.lst_sol_value(*fee_mint, *fee_amount)?;
+        let SwapAccrual {
+            volume_sol_value,
+            fees_sol_value,
+        } = self.swap_accrual;
+        self.swap_accrual = SwapAccrual {
+            volume_sol_value: volume_sol_value
+                .checked_add(volume.into())
+                .ok_or_else(|| anyhow!("Volume accrual overflow"))?,
+            fees_sol_value: fees_sol_value
+                .checked_add(fees.into())
+                .ok_or_else(|| anyhow!("Fees accrual overflow"))?,
+        };
+        Ok(())
+    }
+
+    fn lst_sol_value(&self, mint: Pubkey, amount: u64) -> anyhow::Result<u64> {
+        let sol_val_calc = self
+            .sol_val_calc_for(mint)
+            .ok_or(SPoolError::LstNotSupported(mint))?;
+        Ok(sol_val_calc.lst_to_sol(amount)?.get_min())
+    }
+}
@@ -1,13 +1,13 @@
 use anyhow::anyhow;
-use jupiter_amm_interface::{Quote, QuoteParams, SwapAndAccountMetas, SwapParams};
+use jupiter_amm_interface::{Quote, SwapAndAccountMetas, SwapParams};
 use pricing_programs_interface::PriceLpTokensToMintIxArgs;
-use s_controller_interface::{add_liquidity_ix, AddLiquidityIxArgs, SControllerError};
+use s_controller_interface::{add_liquidity_ix, AddLiquidityIxArgs, PoolState, SControllerError};
 use s_controller_lib::{
     account_metas_extend_with_pricing_program_price_lp_accounts,
     account_metas_extend_with_sol_value_calculator_accounts,
-    add_liquidity_ix_by_mint_full_for_prog, calc_lp_tokens_to_mint, index_to_u32, try_pool_state,
-    AddLiquidityByMintFreeArgs, AddLiquidityIxAmts, AddRemoveLiquidityAccountSuffixes,
-    AddRemoveLiquidityProgramIds, LpTokenRateArgs,
+    add_liquidity_ix_by_mint_full_for_prog, calc_lp_tokens_sol_value, calc_lp_tokens_to_mint,
+    index_to_u32, try_pool_state, AddLiquidityByMintFreeArgs, AddLiquidityIxAmts,
+    AddRemoveLiquidityAccountSuffixes, AddRemoveLiquidityProgramIds, LpTokenRateArgs,
 };
 use s_pricing_prog_aggregate::PricingProg;
 use s_sol_val_calc_prog_aggregate::LstSolValCalc;
@@ -23,33 +23,136 @@ use crate::{LstData, SPool};
 
 use super::{apply_sync_sol_value, calc_quote_fees};
 
+struct AddLiquiditySolValues {
+    lst_amount_sol_value: u64,
+    lst_amount_sol_value_after_fees: u64,
+    lp_tokens_to_mint: u64,
+}
+
 impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
-    pub(crate) fn quote_add_liquidity(
+    /// Quotes the LP tokens minted for depositing `amount` of `input_mint`.
+    ///
+    /// If the LP token supply is zero, the depositor also receives the pool's existing sol value.
+    /// Errors if the input LST's reserves have not been fetched since they're required to sync its sol value.
+    pub fn quote_add_liquidity(&self, input_mint: Pubkey, amount: u64) -> anyhow::Result<Quote> {
+        let pool_state_data = self.pool_state_data()?;
+        let pool_state = try_pool_state(&pool_state_data)?;
+        let lp_token_supply = self.fresh_lp_mint_supply()?;
+
+        let (input_lst_state, input_lst_data) = self.find_ready_input_lst(input_mint)?;
+        let (pool_state, _input_lst_state, _input_reserves_balance) =
+            apply_sync_sol_value(*pool_state, input_lst_state, input_lst_data)?;
+
+        let AddLiquiditySolValues {
+            lst_amount_sol_value,
+            lst_amount_sol_value_after_fees,
+            lp_tokens_to_mint,
+        } = self.add_liquidity_sol_values(
+            &pool_state,
+            lp_token_supply,
+            input_mint,
+            input_lst_data,
+            amount,
+        )?;
+        let (fee_amount, fee_pct) = calc_quote_fees(
+            AmtsAfterFeeBuilder::new_amt_bef_fee(lst_amount_sol_value)
+                .with_amt_aft_fee(lst_amount_sol_value_after_fees)?,
+            &input_lst_data.sol_val_calc,
+        )?;
+        Ok(Quote {
+            not_enough_liquidity: false,
+            min_in_amount: None,
+            min_out_amount: None,
+            in_amount: amount,
+            out_amount: lp_tokens_to_mint,
+            fee_mint: input_mint,
+            fee_amount,
+            fee_pct,
+        })
+    }
+
+    /// Returns the minimum amount of `input_mint` that must be deposited
+    /// to receive at least `lp_out` LP tokens.
+    ///
+    /// Returns 0 if the pool has no LP tokens yet and its existing sol value
+    /// already covers `lp_out`, since the first LP receives that too.
+    pub fn quote_add_liquidity_exact_lp_out(
         &self,
-        QuoteParams {
-            amount, input_mint, ..
-        }: &QuoteParams,
-    ) -> anyhow::Result<Quote> {
+        input_mint: Pubkey,
+        lp_out: u64,
+    ) -> anyhow::Result<u64> {
         let pool_state_data = self.pool_state_data()?;
         let pool_state = try_pool_state(&pool_state_data)?;
-        let pricing_prog = self
-            .pricing_prog
-            .as_ref()
-            .ok_or_else(|| anyhow!("pricing program not fetched"))?;
-        let lp_token_supply = self
-            .lp_mint_supply
-            .ok_or_else(|| anyhow!("LP mint not fetched"))?;
-
-        let (input_lst_state, input_lst_data) = self.find_ready_lst(*input_mint)?;
+        let lp_token_supply = self.fresh_lp_mint_supply()?;
+
+        let (input_lst_state, input_lst_data) = self.find_ready_input_lst(input_mint)?;
         let (pool_state, _input_lst_state, _input_reserves_balance) =
             apply_sync_sol_value(*pool_state, input_lst_state, input_lst_data)?;
 
-        let lst_amount_sol_value = input_lst_data.sol_val_calc.lst_to_sol(*amount)?.get_min();
+        // lower bound, excluding fees
+        let sol_value_needed = if lp_token_supply == 0 {
+            lp_out.saturating_sub(pool_state.total_sol_value)
+        } else if pool_state.total_sol_value == 0 {
+            lp_out
+        } else {
+            calc_lp_tokens_sol_value(
+                LpTokenRateArgs {
+                    lp_token_supply,
+                    pool_total_sol_value: pool_state.total_sol_value,
+                },
+                lp_out,
+            )?
+        };
+        if sol_value_needed == 0 {
+            return Ok(0);
+        }
+        let lp_tokens_for = |amount: u64| {
+            self.add_liquidity_sol_values(
+                &pool_state,
+                lp_token_supply,
+                input_mint,
+                input_lst_data,
+                amount,
+            )
+            .map(|v| v.lp_tokens_to_mint)
+        };
+
+        // invariant: lp_tokens_for(lo) < lp_out <= lp_tokens_for(hi)
+        let mut lo = 0;
+        let mut hi = input_lst_data
+            .sol_val_calc
+            .sol_to_lst(sol_value_needed)?
+            .get_min()
+            .max(1);
+        while lp_tokens_for(hi)? < lp_out {
+            lo = hi;
+            hi = hi.checked_mul(2).ok_or(SControllerError::MathError)?;
+        }
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if lp_tokens_for(mid)? >= lp_out {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        Ok(hi)
+    }
 
+    fn add_liquidity_sol_values(
+        &self,
+        pool_state: &PoolState,
+        lp_token_supply: u64,
+        input_mint: Pubkey,
+        input_lst_data: &LstData,
+        amount: u64,
+    ) -> anyhow::Result<AddLiquiditySolValues> {
+        let pricing_prog = self.pricing_prog()?;
+        let lst_amount_sol_value = input_lst_data.sol_val_calc.lst_to_sol(amount)?.get_min();
         let lst_amount_sol_value_after_fees = pricing_prog.quote_lp_tokens_to_mint(
-            *input_mint,
+            input_mint,
             &PriceLpTokensToMintIxArgs {
-                amount: *amount,
+                amount,
                 sol_value: lst_amount_sol_value,
             },
         )?;
@@ -63,20 +166,10 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             },
             lst_amount_sol_value_after_fees,
         )?;
-        let (fee_amount, fee_pct) = calc_quote_fees(
-            AmtsAfterFeeBuilder::new_amt_bef_fee(lst_amount_sol_value)
-                .with_amt_aft_fee(lst_amount_sol_value_after_fees)?,
-            &input_lst_data.sol_val_calc,
-        )?;
-        Ok(Quote {
-            not_enough_liquidity: false,
-            min_in_amount: None,
-            min_out_amount: None,
-            in_amount: *amount,
-            out_amount: lp_tokens_to_mint,
-            fee_mint: *input_mint,
-            fee_amount,
-            fee_pct,
+        Ok(AddLiquiditySolValues {
+            lst_amount_sol_value,
+            lst_amount_sol_value_after_fees,
+            lp_tokens_to_mint,
         })
     }
 
@@ -97,9 +190,9 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             dst_lp_acc: *destination_token_account,
             pool_state: self
                 .pool_state_account
-                .as_ref()
+                .as_deref()
                 .ok_or_else(|| anyhow!("Pool state not fetched"))?,
-            lst_state_list: &self.lst_state_list_account,
+            lst_state_list: &*self.lst_state_list_account,
             lst_mint: MintWithTokenProgram {
                 pubkey: *source_mint,
                 token_program: *source_token_program,
@@ -107,7 +200,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         })
     }
 
-    pub(crate) fn add_liquidity_ix(&self, swap_params: &SwapParams) -> anyhow::Result<Instruction> {
+    pub fn add_liquidity_ix(&self, swap_params: &SwapParams) -> anyhow::Result<Instruction> {
         let SwapParams {
             in_amount,
             out_amount,
@@ -138,7 +231,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         )?)
     }
 
-    pub(crate) fn add_liquidity_swap_and_account_metas(
+    pub fn add_liquidity_swap_and_account_metas(
         &self,
         swap_params: &SwapParams,
     ) -> anyhow::Result<SwapAndAccountMetas> {
@@ -0,0 +1,101 @@
+use jupiter_amm_interface::Quote;
+use s_controller_interface::{LstState, PoolState};
+use s_controller_lib::{try_lst_state_list, try_pool_state};
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{LstData, SPool};
+
+use super::{apply_sync_sol_value, SyncedSwapLsts};
+
+/// (input_mint, output_mint) -> ExactIn quote
+pub type PairQuote = ((Pubkey, Pubkey), anyhow::Result<Quote>);
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Quotes a SwapExactIn of `amount_per_input` for every ordered pair of
+    /// distinct LSTs whose `lst_data` is `Some`, with the same results as
+    /// quoting each pair individually.
+    ///
+    /// Each LST's sol value is synced once and reused across all pairs it is part of,
+    /// so N LSTs take N SyncSolValues instead of the 2N(N-1) of quoting each pair separately.
+    pub fn quote_all_pairs(&self, amount_per_input: u64) -> Vec<PairQuote> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = match try_lst_state_list(&lst_state_list_data) {
+            Ok(l) => l,
+            Err(_) => return vec![],
+        };
+        let pool_state: Option<PoolState> = self
+            .pool_state_data()
+            .ok()
+            .and_then(|d| try_pool_state(&d).ok().copied());
+        // (lst_state, lst_data, Some((synced lst_state, reserves balance)) if sync succeeded)
+        let synced_lsts: Vec<(&LstState, &LstData, Option<(LstState, u64)>)> = lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .filter_map(|(lst_state, lst_data)| lst_data.as_ref().map(|ld| (lst_state, ld)))
+            .map(|(lst_state, lst_data)| {
+                let synced = pool_state.and_then(|ps| {
+                    apply_sync_sol_value(ps, *lst_state, lst_data)
+                        .ok()
+                        .map(|(_ps, synced, reserves_balance)| (synced, reserves_balance))
+                });
+                (lst_state, lst_data, synced)
+            })
+            .collect();
+
+        let mut res = Vec::with_capacity(synced_lsts.len() * synced_lsts.len());
+        for (input_lst_state, input_lst_data, input_synced) in synced_lsts.iter() {
+            for (output_lst_state, output_lst_data, output_synced) in synced_lsts.iter() {
+                if input_lst_state.mint == output_lst_state.mint {
+                    continue;
+                }
+                let pair = (input_lst_state.mint, output_lst_state.mint);
+                let quote = match (pool_state, input_synced, output_synced) {
+                    (
+                        Some(pool_state),
+                        Some((input_synced, _)),
+                        Some((output_synced, output_reserves_balance)),
+                    ) => with_synced_sol_values(
+                        pool_state,
+                        [
+                            (*input_lst_state, input_synced),
+                            (*output_lst_state, output_synced),
+                        ],
+                    )
+                    .and_then(|pool_state| {
+                        self.quote_swap_exact_in_synced(
+                            &SyncedSwapLsts {
+                                pool_state,
+                                input_mint: input_lst_state.mint,
+                                input_lst_data: *input_lst_data,
+                                output_mint: output_lst_state.mint,
+                                output_lst_data: *output_lst_data,
+                                output_reserves_balance: *output_reserves_balance,
+                            },
+                            amount_per_input,
+                        )
+                    }),
+                    // rerun to get the same error an individual quote would return
+                    _ => self.sync_swap_lsts(pair.0, pair.1).and_then(|synced| {
+                        self.quote_swap_exact_in_synced(&synced, amount_per_input)
+                    }),
+                };
+                res.push((pair, quote));
+            }
+        }
+        res
+    }
+}
+
+/// Applies the sol value changes of already synced LSTs to pool state's total sol value
+fn with_synced_sol_values(
+    mut pool_state: PoolState,
+    lsts: [(&LstState, &LstState); 2],
+) -> anyhow::Result<PoolState> {
+    let total_sol_value = lsts.iter().fold(
+        i128::from(pool_state.total_sol_value),
+        |total, (old, synced)| total - i128::from(old.sol_value) + i128::from(synced.sol_value),
+    );
+    pool_state.total_sol_value = total_sol_value.try_into()?;
+    Ok(pool_state)
+}
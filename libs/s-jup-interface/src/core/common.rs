@@ -4,26 +4,52 @@ use s_controller_interface::{LstState, PoolState};
 use s_controller_lib::sync_sol_value_with_retval;
 use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc};
 use sanctum_token_ratio::AmtsAfterFee;
+use solana_sdk::pubkey::Pubkey;
 
-use crate::LstData;
+use crate::{LstData, SPoolError};
+
+/// State of a pool after running SyncSolValue on both LSTs of a swap,
+/// reusable across multiple quotes of the same pair
+pub struct SyncedSwapLsts<'a> {
+    pub pool_state: PoolState,
+    pub input_mint: Pubkey,
+    pub input_lst_data: &'a LstData,
+    pub output_mint: Pubkey,
+    pub output_lst_data: &'a LstData,
+    pub output_reserves_balance: u64,
+}
 
 /// Returns
 /// (updated pool state, update lst state, reserves balance)
 pub fn apply_sync_sol_value(
     mut pool_state: PoolState,
     mut lst_state: LstState,
+    lst_data: &LstData,
+) -> anyhow::Result<(PoolState, LstState, u64)> {
+    let (reserves_balance, ret_sol_val) = reserves_sol_value(lst_data)?;
+    sync_sol_value_with_retval(&mut pool_state, &mut lst_state, ret_sol_val)?;
+    Ok((pool_state, lst_state, reserves_balance))
+}
+
+/// Returns (reserves balance, sol value of reserves balance),
+/// using the value cached by the latest update if reserves have not changed since
+pub fn reserves_sol_value(
     LstData {
         sol_val_calc,
         reserves_balance,
-        token_program: _,
+        reserves_sol_value,
+        ..
     }: &LstData,
-) -> anyhow::Result<(PoolState, LstState, u64)> {
-    let reserves_balance = *reserves_balance
-        .as_ref()
-        .ok_or_else(|| anyhow!("Reserves balance not fetched"))?;
-    let ret_sol_val = sol_val_calc.lst_to_sol(reserves_balance)?;
-    sync_sol_value_with_retval(&mut pool_state, &mut lst_state, ret_sol_val.get_min())?;
-    Ok((pool_state, lst_state, reserves_balance))
+) -> anyhow::Result<(u64, u64)> {
+    let reserves_balance =
+        reserves_balance.ok_or(SPoolError::ReservesNotFetched(sol_val_calc.lst_mint()))?;
+    let sol_value = match reserves_sol_value {
+        Some((cached_balance, cached_sol_value)) if *cached_balance == reserves_balance => {
+            *cached_sol_value
+        }
+        _ => sol_val_calc.lst_to_sol(reserves_balance)?.get_min(),
+    };
+    Ok((reserves_balance, sol_value))
 }
 
 /// Returns (fee_amount, fee_pct)
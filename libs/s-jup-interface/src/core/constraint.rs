@@ -0,0 +1,46 @@
+use jupiter_amm_interface::{Quote, QuoteParams};
+use s_controller_interface::SControllerError;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{SPool, SPoolError};
+
+/// What limits a quote, see [`SPool::quote_constraint`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteConstraint {
+    /// The quote is not limited by anything
+    Ok,
+    /// The reserves of this LST cannot cover the output
+    Liquidity(Pubkey),
+    /// The input or output amount is worth 0 SOL
+    ZeroValue,
+    /// The pricing program would output more SOL value than is put in
+    PoolWouldLoseSolValue,
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Quotes `quote_params` and returns the factor that limits it.
+    ///
+    /// Errors that are not one of [`QuoteConstraint`]'s variants,
+    /// e.g. accounts not being fetched, are returned as is.
+    pub fn quote_constraint(
+        &self,
+        quote_params: &QuoteParams,
+    ) -> Result<QuoteConstraint, SPoolError> {
+        match self.quote_full(quote_params) {
+            Ok(Quote {
+                not_enough_liquidity: true,
+                ..
+            }) => Ok(QuoteConstraint::Liquidity(quote_params.output_mint)),
+            Ok(_) => Ok(QuoteConstraint::Ok),
+            Err(SPoolError::InsufficientLiquidity(mint)) => Ok(QuoteConstraint::Liquidity(mint)),
+            Err(SPoolError::SController(SControllerError::ZeroValue)) => {
+                Ok(QuoteConstraint::ZeroValue)
+            }
+            Err(SPoolError::SController(SControllerError::PoolWouldLoseSolValue)) => {
+                Ok(QuoteConstraint::PoolWouldLoseSolValue)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use anyhow::anyhow;
+use jupiter_amm_interface::{Quote, QuoteParams, SwapMode};
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_token_ratio::BPS_DENOMINATOR;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::SPool;
+
+use super::{QuoteTrace, SyncedSwapLsts};
+
+/// Components of the amount of output LST deducted from a SwapExactIn, compared to converting
+/// the input's sol value into the output LST with no fees at all.
+/// All amounts are in output LST atomic units.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    /// Pricing program fee that stays in the pool for LPs
+    pub lp_fee: u64,
+    /// Share of the pricing program fee transferred to the protocol fee accumulator
+    pub protocol_fee: u64,
+    /// Lost to rounding in the sol value calculators' conversions
+    pub calc_fee: u64,
+}
+
+impl FeeBreakdown {
+    pub const fn total(&self) -> u64 {
+        self.lp_fee
+            .saturating_add(self.protocol_fee)
+            .saturating_add(self.calc_fee)
+    }
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Fraction of output lost beyond the quote's fee compared to
+    /// converting the input's sol value into the output LST with no fees at all,
+    /// e.g. due to rounding.
+    ///
+    /// Flat fee pricing, the only pricing program supported, charges the same fee rate
+    /// regardless of swap size or reserves, so this does not grow with swap size:
+    /// it only measures rounding in the sol value and fee conversions.
+    ///
+    /// Only ExactIn LST -> LST swaps are supported.
+    pub fn price_impact(&self, quote_params: &QuoteParams) -> anyhow::Result<Decimal> {
+        self.quote_and_price_impact(quote_params)
+            .map(|(_quote, impact)| impact)
+    }
+
+    /// Total cost of a swap in bps: the quote's `fee_pct` + [`Self::price_impact`].
+    ///
+    /// Since flat fee price impact is only rounding, this is about the same for all swap sizes.
+    pub fn effective_cost_bps(&self, quote_params: &QuoteParams) -> anyhow::Result<Decimal> {
+        let (Quote { fee_pct, .. }, impact) = self.quote_and_price_impact(quote_params)?;
+        fee_pct
+            .checked_add(impact)
+            .and_then(|total| total.checked_mul(Decimal::from(BPS_DENOMINATOR)))
+            .ok_or_else(|| anyhow!("Decimal effective cost overflow"))
+    }
+
+    /// Largest slippage in bps that the swap can suffer while its output is still
+    /// worth more than the fee paid for it: the largest `bps` where
+    /// `out_amount * (10_000 - bps) > fee_amount * 10_000`.
+    ///
+    /// Returns 0 if the quoted output does not exceed the fee.
+    /// Only routes that charge fees in the output mint are supported,
+    /// so add liquidity is not.
+    pub fn max_tolerable_slippage_bps(&self, quote_params: &QuoteParams) -> anyhow::Result<u16> {
+        let Quote {
+            out_amount,
+            fee_amount,
+            fee_mint,
+            ..
+        } = self.quote_full(quote_params)?;
+        if fee_mint != quote_params.output_mint {
+            return Err(anyhow!(
+                "Fees not charged in output mint {}",
+                quote_params.output_mint
+            ));
+        }
+        if out_amount == 0 {
+            return Err(anyhow!("Quoted output is zero"));
+        }
+        let bps_denom = u128::from(BPS_DENOMINATOR);
+        let fee_bps_floor = u128::from(fee_amount) * bps_denom / u128::from(out_amount);
+        let max_bps = bps_denom.saturating_sub(fee_bps_floor.saturating_add(1));
+        Ok(max_bps.try_into()?)
+    }
+
+    /// Splits what a SwapExactIn deducts from its output into [`FeeBreakdown`]'s components.
+    ///
+    /// The quote's `fee_amount` is `lp_fee + protocol_fee`.
+    /// See [`Self::quote_swap_exact_in_detailed`] to get the quote along with it.
+    pub fn quote_swap_exact_in_fees(
+        &self,
+        quote_params: &QuoteParams,
+    ) -> anyhow::Result<FeeBreakdown> {
+        self.quote_swap_exact_in_detailed(quote_params)
+            .map(|(_quote, fees)| fees)
+    }
+
+    /// Amount of `mint_a` lost by swapping `amount` of `mint_a` to `mint_b` and
+    /// the output back to `mint_a`, both ExactIn.
+    ///
+    /// Both legs are quoted against the current pool state,
+    /// i.e. the first leg's effect on reserves is not applied before the second.
+    pub fn round_trip_loss(
+        &self,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        amount: u64,
+    ) -> anyhow::Result<u64> {
+        let Quote {
+            out_amount: b_amount,
+            ..
+        } = self.quote_full(&QuoteParams {
+            amount,
+            input_mint: mint_a,
+            output_mint: mint_b,
+            swap_mode: SwapMode::ExactIn,
+        })?;
+        let Quote {
+            out_amount: a_amount,
+            ..
+        } = self.quote_full(&QuoteParams {
+            amount: b_amount,
+            input_mint: mint_b,
+            output_mint: mint_a,
+            swap_mode: SwapMode::ExactIn,
+        })?;
+        amount.checked_sub(a_amount).ok_or_else(|| {
+            anyhow!(
+                "Round trip {mint_a} -> {mint_b} -> {mint_a} gained {}",
+                a_amount - amount
+            )
+        })
+    }
+
+    fn quote_and_price_impact(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+        }: &QuoteParams,
+    ) -> anyhow::Result<(Quote, Decimal)> {
+        if *swap_mode != SwapMode::ExactIn {
+            return Err(anyhow!("price impact only supported for ExactIn"));
+        }
+        let synced = self.sync_swap_lsts(*input_mint, *output_mint)?;
+        let quote = self.quote_swap_exact_in_synced(&synced, *amount)?;
+        let impact = price_impact_synced(&synced, *amount, &quote)?;
+        Ok((quote, impact))
+    }
+}
+
+/// Amount of output LST `amount` of input LST converts to with no fees
+fn no_fee_out_amount(
+    SyncedSwapLsts {
+        input_lst_data,
+        output_lst_data,
+        ..
+    }: &SyncedSwapLsts,
+    amount: u64,
+) -> anyhow::Result<u64> {
+    Ok(output_lst_data
+        .sol_val_calc
+        .sol_to_lst(input_lst_data.sol_val_calc.lst_to_sol(amount)?.get_min())?
+        .get_min())
+}
+
+pub(crate) fn fee_breakdown_synced(
+    synced: &SyncedSwapLsts,
+    amount: u64,
+    QuoteTrace {
+        quote: Quote {
+            out_amount,
+            fee_amount,
+            ..
+        },
+        to_protocol_fees_lst_amount,
+        ..
+    }: &QuoteTrace,
+) -> anyhow::Result<FeeBreakdown> {
+    let total_deducted = no_fee_out_amount(synced, amount)?.saturating_sub(*out_amount);
+    let lp_fee = fee_amount.saturating_sub(*to_protocol_fees_lst_amount);
+    Ok(FeeBreakdown {
+        lp_fee,
+        protocol_fee: *to_protocol_fees_lst_amount,
+        calc_fee: total_deducted
+            .saturating_sub(lp_fee.saturating_add(*to_protocol_fees_lst_amount)),
+    })
+}
+
+pub(crate) fn price_impact_synced(
+    synced: &SyncedSwapLsts,
+    amount: u64,
+    Quote {
+        out_amount,
+        fee_amount,
+        ..
+    }: &Quote,
+) -> anyhow::Result<Decimal> {
+    let no_fee_out_amount = no_fee_out_amount(synced, amount)?;
+    if no_fee_out_amount == 0 {
+        return Ok(Decimal::ZERO);
+    }
+    let actual = Decimal::from_u64(out_amount.saturating_add(*fee_amount))
+        .ok_or_else(|| anyhow!("Decimal conv error out_amount"))?;
+    let expected = Decimal::from_u64(no_fee_out_amount)
+        .ok_or_else(|| anyhow!("Decimal conv error no_fee_out_amount"))?;
+    let ratio = actual
+        .checked_div(expected)
+        .ok_or_else(|| anyhow!("Decimal impact div err"))?;
+    Ok(Decimal::ONE - ratio)
+}
@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+use jupiter_amm_interface::Quote;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::SPool;
+
+use super::price_impact_synced;
+
+pub const PRICE_CURVE_CSV_HEADER: &str = "amount,out_amount,fee,impact";
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Quotes a SwapExactIn of input_mint -> output_mint for each of `amounts`,
+    /// running SyncSolValue on both LSTs only once for the whole curve
+    pub fn quote_swap_exact_in_curve(
+        &self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        amounts: &[u64],
+    ) -> anyhow::Result<Vec<Quote>> {
+        let synced = self.sync_swap_lsts(input_mint, output_mint)?;
+        amounts
+            .iter()
+            .map(|amount| self.quote_swap_exact_in_synced(&synced, *amount))
+            .collect()
+    }
+
+    /// Exports [`Self::quote_swap_exact_in_curve`] as CSV with header [`PRICE_CURVE_CSV_HEADER`].
+    ///
+    /// `fee` is in output LST atomics.
+    /// `impact` is as defined in [`Self::price_impact`].
+    pub fn price_curve_csv(
+        &self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        amounts: &[u64],
+    ) -> anyhow::Result<String> {
+        let synced = self.sync_swap_lsts(input_mint, output_mint)?;
+        let mut res = String::from(PRICE_CURVE_CSV_HEADER);
+        res.push('\n');
+        for amount in amounts {
+            let quote = self.quote_swap_exact_in_synced(&synced, *amount)?;
+            let impact = price_impact_synced(&synced, *amount, &quote)?;
+            let Quote {
+                out_amount,
+                fee_amount,
+                ..
+            } = quote;
+            writeln!(res, "{amount},{out_amount},{fee_amount},{impact}")?;
+        }
+        Ok(res)
+    }
+}
@@ -7,18 +7,36 @@ use s_controller_lib::try_lst_state_list;
 use solana_readonly_account::ReadonlyAccountData;
 use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
 
-use crate::SPool;
+use crate::{SPool, SPoolError};
 
+mod account_limit;
+mod accrual;
 mod add_liquidity;
+mod all_pairs;
 mod common;
+mod constraint;
+mod cost;
+mod curve;
 mod remove_liquidity;
+mod shadow;
 mod swap_exact_in;
 mod swap_exact_out;
+mod synthetic;
+mod valuation;
 
+pub use account_limit::*;
+pub use accrual::*;
 pub use add_liquidity::*;
+pub use all_pairs::*;
+pub use constraint::*;
+pub use cost::*;
+pub use curve::*;
 pub use remove_liquidity::*;
+pub use shadow::*;
 pub use swap_exact_in::*;
 pub use swap_exact_out::*;
+pub use synthetic::*;
+pub use valuation::*;
 
 use common::*;
 
@@ -48,24 +66,87 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         }
     }
 
-    pub fn quote_full(&self, quote_params: &QuoteParams) -> anyhow::Result<Quote> {
+    /// Same as [`Self::swap_ix`], but with the slippage guard set explicitly to
+    /// `other_amount_threshold`, following jupiter's semantics:
+    /// - ExactIn: the minimum output amount, `swap_params.in_amount` is the amount in
+    /// - ExactOut: the maximum input amount, `swap_params.out_amount` is the amount out
+    ///
+    /// [`Self::swap_ix`] instead uses the other amount of `swap_params` as the threshold,
+    /// so callers passing unadjusted quoted amounts to it get zero slippage tolerance.
+    pub fn swap_ix_with_other_amount_threshold(
+        &self,
+        swap_params: &SwapParams,
+        swap_mode: SwapMode,
+        other_amount_threshold: u64,
+    ) -> anyhow::Result<Instruction> {
         let lp_mint = self.lp_token_mint()?;
-        if quote_params.input_mint == lp_mint {
+        if swap_params.source_mint == lp_mint || swap_params.destination_mint == lp_mint {
+            return Err(anyhow!(
+                "other_amount_threshold not supported for add/remove liquidity"
+            ));
+        }
+        match swap_mode {
+            SwapMode::ExactIn => {
+                self.swap_exact_in_ix_with_min_out(swap_params, other_amount_threshold)
+            }
+            SwapMode::ExactOut => {
+                self.swap_exact_out_ix_with_max_in(swap_params, other_amount_threshold)
+            }
+        }
+    }
+
+    /// SyncSolValue instructions for the LSTs of `swap_params` followed by the instruction
+    /// from [`Self::swap_ix`], so that the swap executes against freshly synced sol values
+    /// in the same transaction.
+    ///
+    /// For add and remove liquidity, only the LST is synced.
+    pub fn sync_and_swap_ixs(
+        &self,
+        swap_params: &SwapParams,
+        swap_mode: SwapMode,
+    ) -> anyhow::Result<Vec<Instruction>> {
+        let lp_mint = self.lp_token_mint()?;
+        let mut ixs = [swap_params.source_mint, swap_params.destination_mint]
+            .into_iter()
+            .filter(|mint| *mint != lp_mint)
+            .map(|mint| self.sync_sol_value_ix_by_mint(mint))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        ixs.push(self.swap_ix(swap_params, swap_mode)?);
+        Ok(ixs)
+    }
+
+    /// Whether ExactOut is supported from `input_mint` to `output_mint`:
+    /// true for LST -> LST swaps, false for add and remove liquidity, which are ExactIn only.
+    ///
+    /// False if pool state has not been fetched since the LP token mint is then unknown.
+    pub fn supports_exact_out_for(&self, input_mint: Pubkey, output_mint: Pubkey) -> bool {
+        self.lp_token_mint().map_or(false, |lp_mint| {
+            input_mint != lp_mint && output_mint != lp_mint
+        })
+    }
+
+    pub fn quote_full(&self, quote_params: &QuoteParams) -> Result<Quote, SPoolError> {
+        if quote_params.input_mint == quote_params.output_mint {
+            return Err(anyhow!("Cannot swap {} to itself", quote_params.input_mint).into());
+        }
+        let lp_mint = self.lp_token_mint()?;
+        let quote = if quote_params.input_mint == lp_mint {
             if let SwapMode::ExactOut = quote_params.swap_mode {
-                return Err(anyhow!("ExactOut not supported for remove liquidity"));
+                return Err(anyhow!("ExactOut not supported for remove liquidity").into());
             }
-            self.quote_remove_liquidity(quote_params)
+            self.quote_remove_liquidity(quote_params.output_mint, quote_params.amount)?
         } else if quote_params.output_mint == lp_mint {
             if let SwapMode::ExactOut = quote_params.swap_mode {
-                return Err(anyhow!("ExactOut not supported for add liquidity"));
+                return Err(anyhow!("ExactOut not supported for add liquidity").into());
             }
-            self.quote_add_liquidity(quote_params)
+            self.quote_add_liquidity(quote_params.input_mint, quote_params.amount)?
         } else {
             match quote_params.swap_mode {
-                SwapMode::ExactIn => self.quote_swap_exact_in(quote_params),
-                SwapMode::ExactOut => self.quote_swap_exact_out(quote_params),
+                SwapMode::ExactIn => self.quote_swap_exact_in(quote_params)?,
+                SwapMode::ExactOut => self.quote_swap_exact_out(quote_params)?,
             }
-        }
+        };
+        Ok(quote)
     }
 
     pub fn get_swap_and_account_metas_full(
@@ -88,7 +169,12 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         }
     }
 
-    /// Returns all mints this SPool can swap between (includes LP token mint)
+    /// Returns all mints this SPool can swap between (includes LP token mint).
+    ///
+    /// LST mints are read from the lst_state_list account this SPool was constructed with,
+    /// so they are available right after construction, e.g. from `Amm::from_keyed_account()`,
+    /// before any `update()`. The LP token mint is read from pool state, so it is only
+    /// included once pool state has been fetched.
     pub fn get_reserve_mints_full(&self) -> Vec<Pubkey> {
         let lst_state_list_data = self.lst_state_list_account.data();
         let mut res: Vec<Pubkey> = try_lst_state_list(&lst_state_list_data).map_or_else(
@@ -96,8 +182,37 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             |list| list.iter().map(|LstState { mint, .. }| *mint).collect(),
         );
         if let Ok(lp_token_mint) = self.lp_token_mint() {
-            res.push(lp_token_mint);
+            // dont duplicate the LP mint if the pool is misconfigured to also list it as an LST,
+            // see validate_lp_mint_not_listed()
+            if !res.contains(&lp_token_mint) {
+                res.push(lp_token_mint);
+            }
         }
         res
     }
 }
+
+/// Converts the output of [`SPool::get_swap_and_account_metas_full`] into an [`Instruction`]
+/// invoking `program_id` directly, e.g. to compare against or replace one built with
+/// [`SPool::swap_ix`].
+///
+/// [`SwapAndAccountMetas`] does not include the swap amounts, so `data` must be the
+/// serialized instruction data. Errors if the first account is not `program_id`, which the
+/// metas are prefixed with for the jup program to CPI into.
+pub fn swap_and_account_metas_to_ix(
+    program_id: Pubkey,
+    SwapAndAccountMetas { account_metas, .. }: SwapAndAccountMetas,
+    data: Vec<u8>,
+) -> anyhow::Result<Instruction> {
+    let mut account_metas = account_metas.into_iter();
+    match account_metas.next() {
+        Some(first) if first.pubkey == program_id => Ok(Instruction {
+            program_id,
+            accounts: account_metas.collect(),
+            data,
+        }),
+        _ => Err(anyhow!(
+            "account metas not prefixed with program {program_id}"
+        )),
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use jupiter_amm_interface::{Quote, QuoteParams, SwapAndAccountMetas, SwapParams};
+use jupiter_amm_interface::{Quote, SwapAndAccountMetas, SwapParams};
 use pricing_programs_interface::PriceLpTokensToRedeemIxArgs;
 use s_controller_interface::{remove_liquidity_ix, RemoveLiquidityIxArgs, SControllerError};
 use s_controller_lib::{
@@ -25,25 +25,24 @@ use crate::{LstData, SPool};
 use super::{apply_sync_sol_value, calc_quote_fees};
 
 impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
-    pub(crate) fn quote_remove_liquidity(
+    /// Quotes the amount of `output_mint` received for burning `lp_amount` LP tokens.
+    ///
+    /// `not_enough_liquidity` is set if the output LST's reserves cannot cover
+    /// the amount to the user plus protocol fees.
+    pub fn quote_remove_liquidity(
         &self,
-        QuoteParams {
-            amount,
-            output_mint,
-            ..
-        }: &QuoteParams,
+        output_mint: Pubkey,
+        lp_amount: u64,
     ) -> anyhow::Result<Quote> {
         let pool_state_data = self.pool_state_data()?;
         let pool_state = try_pool_state(&pool_state_data)?;
-        let pricing_prog = self
-            .pricing_prog
-            .as_ref()
-            .ok_or_else(|| anyhow!("pricing program not fetched"))?;
-        let lp_token_supply = self
-            .lp_mint_supply
-            .ok_or_else(|| anyhow!("LP mint not fetched"))?;
+        let pricing_prog = self.pricing_prog()?;
+        let lp_token_supply = self.fresh_lp_mint_supply()?;
+        if lp_token_supply == 0 {
+            return Err(anyhow!("LP token supply is zero, nothing to redeem"));
+        }
 
-        let (output_lst_state, output_lst_data) = self.find_ready_lst(*output_mint)?;
+        let (output_lst_state, output_lst_data) = self.find_ready_lst(output_mint)?;
         let (pool_state, _output_lst_state, output_reserves_balance) =
             apply_sync_sol_value(*pool_state, output_lst_state, output_lst_data)?;
 
@@ -53,13 +52,13 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
                 lp_token_supply,
                 pool_total_sol_value,
             },
-            *amount,
+            lp_amount,
         )?;
 
         let lp_tokens_sol_value_after_fees = pricing_prog.quote_lp_tokens_to_redeem(
-            *output_mint,
+            output_mint,
             &PriceLpTokensToRedeemIxArgs {
-                amount: *amount,
+                amount: lp_amount,
                 sol_value: lp_tokens_sol_value,
             },
         )?;
@@ -90,9 +89,9 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             not_enough_liquidity,
             min_in_amount: None,
             min_out_amount: None,
-            in_amount: *amount,
+            in_amount: lp_amount,
             out_amount: to_user_lst_amount,
-            fee_mint: *output_mint,
+            fee_mint: output_mint,
             fee_amount,
             fee_pct,
         })
@@ -115,9 +114,9 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             dst_lst_acc: *destination_token_account,
             pool_state: self
                 .pool_state_account
-                .as_ref()
+                .as_deref()
                 .ok_or_else(|| anyhow!("Pool state not fetched"))?,
-            lst_state_list: &self.lst_state_list_account,
+            lst_state_list: &*self.lst_state_list_account,
             lst_mint: MintWithTokenProgram {
                 pubkey: *destination_mint,
                 token_program: *source_token_program,
@@ -125,10 +124,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         })
     }
 
-    pub(crate) fn remove_liquidity_ix(
-        &self,
-        swap_params: &SwapParams,
-    ) -> anyhow::Result<Instruction> {
+    pub fn remove_liquidity_ix(&self, swap_params: &SwapParams) -> anyhow::Result<Instruction> {
         let SwapParams {
             in_amount,
             out_amount,
@@ -159,7 +155,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         )?)
     }
 
-    pub(crate) fn remove_liquidity_swap_and_account_metas(
+    pub fn remove_liquidity_swap_and_account_metas(
         &self,
         swap_params: &SwapParams,
     ) -> anyhow::Result<SwapAndAccountMetas> {
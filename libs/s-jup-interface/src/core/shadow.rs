@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use jupiter_amm_interface::{Quote, QuoteParams};
+use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc};
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::SPool;
+
+impl<S: ReadonlyAccountData + Clone, L: ReadonlyAccountData + Clone> SPool<S, L> {
+    /// Quotes `quote_params` as [`Self::quote_full`] would if the LSTs keyed in `shadows`
+    /// used the given sol value calculators instead of their current ones, e.g. to compare
+    /// a new calculator's output against the deployed one before switching to it on chain.
+    ///
+    /// Shadow calculators must already be updated with their accounts.
+    /// Quotes a clone, so `self` is unchanged.
+    pub fn quote_with_shadow_calcs(
+        &self,
+        quote_params: &QuoteParams,
+        shadows: &HashMap<Pubkey, KnownLstSolValCalc>,
+    ) -> anyhow::Result<Quote> {
+        if let Some((mint, calc)) = shadows
+            .iter()
+            .find(|(mint, calc)| calc.lst_mint() != **mint)
+        {
+            return Err(anyhow!(
+                "Shadow calc for {mint} is for a different LST {}",
+                calc.lst_mint()
+            ));
+        }
+        let mut shadowed = self.clone();
+        shadowed
+            .lst_data_list
+            .iter_mut()
+            .flatten()
+            .for_each(|lst_data| {
+                if let Some(shadow) = shadows.get(&lst_data.sol_val_calc.lst_mint()) {
+                    lst_data.sol_val_calc = shadow.clone();
+                    if let Some(clock) = &self.frozen_clock {
+                        lst_data.sol_val_calc.set_clock(clock.clone());
+                    }
+                    // cached with the replaced calc
+                    lst_data.reserves_sol_value = None;
+                }
+            });
+        Ok(shadowed.quote_full(quote_params)?)
+    }
+}
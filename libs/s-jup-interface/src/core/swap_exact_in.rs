@@ -1,7 +1,10 @@
 use anyhow::anyhow;
-use jupiter_amm_interface::{Quote, QuoteParams, SwapAndAccountMetas, SwapParams};
+use jupiter_amm_interface::{Quote, QuoteParams, SwapAndAccountMetas, SwapMode, SwapParams};
 use pricing_programs_interface::{PriceExactInIxArgs, PriceExactInKeys};
-use s_controller_interface::{swap_exact_in_ix, SControllerError, SwapExactInIxArgs};
+use s_controller_interface::{
+    swap_exact_in_ix, PoolState, SControllerError, SwapExactInIxArgs, SwapExactInIxData,
+    SWAP_EXACT_IN_IX_DISCM,
+};
 use s_controller_lib::{
     account_metas_extend_with_pricing_program_price_swap_accounts,
     account_metas_extend_with_src_dst_sol_value_calculator_accounts, calc_swap_protocol_fees,
@@ -13,18 +16,193 @@ use s_controller_lib::{
 use s_pricing_prog_aggregate::PricingProg;
 use s_sol_val_calc_prog_aggregate::LstSolValCalc;
 use sanctum_token_lib::MintWithTokenProgram;
-use sanctum_token_ratio::AmtsAfterFeeBuilder;
+use sanctum_token_ratio::{AmtsAfterFeeBuilder, BPS_DENOMINATOR};
 use solana_readonly_account::ReadonlyAccountData;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
 
-use crate::{LstData, SPool};
+use crate::{LstData, SPool, SPoolError};
+
+use super::{
+    apply_sync_sol_value, calc_quote_fees, fee_breakdown_synced, FeeBreakdown, SyncedSwapLsts,
+};
+
+/// A built SwapExactIn instruction, decoded for inspection
+#[derive(Clone, Debug, PartialEq)]
+pub struct BuiltSwap {
+    pub program_id: Pubkey,
+    pub discriminant: u8,
+    pub args: SwapExactInIxArgs,
+    pub account_metas: Vec<AccountMeta>,
+}
+
+impl BuiltSwap {
+    pub fn src_lst_index(&self) -> u32 {
+        self.args.src_lst_index
+    }
+
+    pub fn dst_lst_index(&self) -> u32 {
+        self.args.dst_lst_index
+    }
+
+    pub fn src_lst_value_calc_accs(&self) -> u8 {
+        self.args.src_lst_value_calc_accs
+    }
+
+    pub fn dst_lst_value_calc_accs(&self) -> u8 {
+        self.args.dst_lst_value_calc_accs
+    }
+}
 
-use super::{apply_sync_sol_value, calc_quote_fees};
+impl TryFrom<Instruction> for BuiltSwap {
+    type Error = std::io::Error;
+
+    fn try_from(
+        Instruction {
+            program_id,
+            accounts,
+            data,
+        }: Instruction,
+    ) -> Result<Self, Self::Error> {
+        let SwapExactInIxData(args) = SwapExactInIxData::deserialize(&data)?;
+        Ok(Self {
+            program_id,
+            discriminant: SWAP_EXACT_IN_IX_DISCM,
+            args,
+            account_metas: accounts,
+        })
+    }
+}
+
+/// Intermediate values of a SwapExactIn quote, see [`SPool::quote_swap_exact_in_trace`]
+#[derive(Clone, Debug)]
+pub struct QuoteTrace {
+    pub quote: Quote,
+    /// Input LST SyncSolValue was run on before pricing
+    pub synced_input_mint: Pubkey,
+    /// Output LST SyncSolValue was run on before pricing, after the input LST
+    pub synced_output_mint: Pubkey,
+    /// Pool's total sol value after both syncs
+    pub synced_total_sol_value: u64,
+    /// Sol value of the input amount
+    pub in_sol_value: u64,
+    /// Sol value of the output priced by the pricing program
+    pub out_sol_value: u64,
+    /// Output LST transferred to the user, the quote's `out_amount`
+    pub dst_lst_out: u64,
+    /// Output LST transferred to the protocol fee accumulator
+    pub to_protocol_fees_lst_amount: u64,
+}
+
+/// The user accounts of a swap instruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapAccounts {
+    pub signer: Pubkey,
+    pub src_lst_acc: Pubkey,
+    pub dst_lst_acc: Pubkey,
+}
 
 impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Quotes a SwapExactIn and builds its instruction from the same synced pool state,
+    /// with `min_amount_out` set to the quoted output less `slippage_bps`, rounded down.
+    ///
+    /// The instruction is still returned if the quote has `not_enough_liquidity` set.
+    pub fn quote_and_build_exact_in(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+        }: &QuoteParams,
+        SwapAccounts {
+            signer,
+            src_lst_acc,
+            dst_lst_acc,
+        }: SwapAccounts,
+        slippage_bps: u16,
+    ) -> anyhow::Result<(Quote, Instruction)> {
+        if let SwapMode::ExactOut = swap_mode {
+            return Err(anyhow!(
+                "quote_and_build_exact_in does not support ExactOut"
+            ));
+        }
+        if slippage_bps > BPS_DENOMINATOR {
+            return Err(anyhow!("slippage of {slippage_bps} bps exceeds 100%"));
+        }
+        let synced = self.sync_swap_lsts(*input_mint, *output_mint)?;
+        let quote = self.quote_swap_exact_in_synced(&synced, *amount)?;
+        let min_amount_out: u64 = (u128::from(quote.out_amount)
+            * u128::from(BPS_DENOMINATOR - slippage_bps)
+            / u128::from(BPS_DENOMINATOR))
+        .try_into()?;
+        let jupiter_program_id = Pubkey::default();
+        let ix = self.swap_exact_in_ix(&SwapParams {
+            in_amount: *amount,
+            out_amount: min_amount_out,
+            source_mint: *input_mint,
+            destination_mint: *output_mint,
+            source_token_account: src_lst_acc,
+            destination_token_account: dst_lst_acc,
+            token_transfer_authority: signer,
+            open_order_address: None,
+            quote_mint_to_referrer: None,
+            // unused, the instruction is for the pool program directly
+            jupiter_program_id: &jupiter_program_id,
+            missing_dynamic_accounts_as_default: false,
+        })?;
+        Ok((quote, ix))
+    }
+
+    /// Same as the SwapExactIn quote from [`Self::quote_full`], along with its fees split into
+    /// [`FeeBreakdown`]'s components, both computed from the same synced pool state
+    pub fn quote_swap_exact_in_detailed(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+        }: &QuoteParams,
+    ) -> anyhow::Result<(Quote, FeeBreakdown)> {
+        if let SwapMode::ExactOut = swap_mode {
+            return Err(anyhow!(
+                "quote_swap_exact_in_detailed does not support ExactOut"
+            ));
+        }
+        let synced = self.sync_swap_lsts(*input_mint, *output_mint)?;
+        let trace = self.quote_swap_exact_in_synced_trace(&synced, *amount)?;
+        let fees = fee_breakdown_synced(&synced, *amount, &trace)?;
+        Ok((trace.quote, fees))
+    }
+
+    /// Same as the SwapExactIn quote from [`Self::quote_full`], along with the intermediate
+    /// values it was computed from, e.g. for reconciling quotes against onchain swaps
+    pub fn quote_swap_exact_in_trace(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+        }: &QuoteParams,
+    ) -> anyhow::Result<QuoteTrace> {
+        if let SwapMode::ExactOut = swap_mode {
+            return Err(anyhow!(
+                "quote_swap_exact_in_trace does not support ExactOut"
+            ));
+        }
+        let synced = self.sync_swap_lsts(*input_mint, *output_mint)?;
+        self.quote_swap_exact_in_synced_trace(&synced, *amount)
+    }
+
+    /// Same as the instruction from [`Self::swap_ix`] for SwapExactIn, but decoded
+    pub fn build_swap_exact_in(&self, swap_params: &SwapParams) -> anyhow::Result<BuiltSwap> {
+        Ok(self.swap_exact_in_ix(swap_params)?.try_into()?)
+    }
+
     pub(crate) fn quote_swap_exact_in(
         &self,
         QuoteParams {
@@ -34,21 +212,136 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             ..
         }: &QuoteParams,
     ) -> anyhow::Result<Quote> {
+        self.sync_swap_lsts(*input_mint, *output_mint)
+            .and_then(|synced| self.quote_swap_exact_in_synced(&synced, *amount))
+            .map_err(|e| SPoolError::from(e).into_anyhow())
+    }
+
+    /// FOR ANALYSIS ONLY, the returned quote does not reflect what the program would output.
+    ///
+    /// Quotes a SwapExactIn but passes `in_sol_value_override` to the pricing program's
+    /// PriceExactIn as `sol_value` instead of the input amount's actual sol value,
+    /// for testing the sensitivity of the pricing program's output to its input.
+    pub fn quote_swap_exact_in_with_pricing_input_override(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            ..
+        }: &QuoteParams,
+        in_sol_value_override: u64,
+    ) -> anyhow::Result<Quote> {
+        let synced = self.sync_swap_lsts(*input_mint, *output_mint)?;
+        self.quote_swap_exact_in_synced_inner(&synced, *amount, Some(in_sol_value_override))
+            .map(|QuoteTrace { quote, .. }| quote)
+    }
+
+    /// FOR DEBUGGING, e.g. reproducing a specific onchain swap.
+    ///
+    /// Quotes a SwapExactIn with `src_ret` and `dst_ret` as the sol values returned by the
+    /// input and output LSTs' SyncSolValue instead of computing them from reserves with the
+    /// sol value calculators. Returns the quote and the pool state after both syncs.
+    ///
+    /// The quoted amounts only depend on the calculators' conversions of the swapped amounts,
+    /// so the injected values are only reflected in the returned pool state.
+    pub fn quote_swap_exact_in_with_ret_sol_vals(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+        }: &QuoteParams,
+        src_ret: u64,
+        dst_ret: u64,
+    ) -> anyhow::Result<(Quote, PoolState)> {
+        if let SwapMode::ExactOut = swap_mode {
+            return Err(anyhow!(
+                "quote_swap_exact_in_with_ret_sol_vals does not support ExactOut"
+            ));
+        }
+        let pool_state_data = self.pool_state_data()?;
+        let mut pool_state = *try_pool_state(&pool_state_data)?;
+
+        let (mut input_lst_state, input_lst_data) = self.find_ready_input_lst(*input_mint)?;
+        sync_sol_value_with_retval(&mut pool_state, &mut input_lst_state, src_ret)?;
+        let (mut output_lst_state, output_lst_data) = self.find_ready_lst(*output_mint)?;
+        sync_sol_value_with_retval(&mut pool_state, &mut output_lst_state, dst_ret)?;
+        let output_reserves_balance = output_lst_data
+            .reserves_balance
+            .ok_or(SPoolError::ReservesNotFetched(*output_mint))?;
+
+        let synced = SyncedSwapLsts {
+            pool_state,
+            input_mint: *input_mint,
+            input_lst_data,
+            output_mint: *output_mint,
+            output_lst_data,
+            output_reserves_balance,
+        };
+        let quote = self.quote_swap_exact_in_synced(&synced, *amount)?;
+        Ok((quote, pool_state))
+    }
+
+    /// Runs SyncSolValue on both the input and output LSTs of a swap
+    pub(crate) fn sync_swap_lsts(
+        &self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+    ) -> anyhow::Result<SyncedSwapLsts<'_>> {
         let pool_state_data = self.pool_state_data()?;
         let pool_state = try_pool_state(&pool_state_data)?;
-        let pricing_prog = self
-            .pricing_prog
-            .as_ref()
-            .ok_or_else(|| anyhow!("pricing program not fetched"))?;
 
-        let (input_lst_state, input_lst_data) = self.find_ready_lst(*input_mint)?;
+        let (input_lst_state, input_lst_data) = self.find_ready_input_lst(input_mint)?;
         let (pool_state, _input_lst_state, _input_reserves_balance) =
             apply_sync_sol_value(*pool_state, input_lst_state, input_lst_data)?;
-        let (output_lst_state, output_lst_data) = self.find_ready_lst(*output_mint)?;
+        let (output_lst_state, output_lst_data) = self.find_ready_lst(output_mint)?;
         let (pool_state, _output_lst_state, output_reserves_balance) =
             apply_sync_sol_value(pool_state, output_lst_state, output_lst_data)?;
+        Ok(SyncedSwapLsts {
+            pool_state,
+            input_mint,
+            input_lst_data,
+            output_mint,
+            output_lst_data,
+            output_reserves_balance,
+        })
+    }
+
+    pub(crate) fn quote_swap_exact_in_synced(
+        &self,
+        synced: &SyncedSwapLsts,
+        amount: u64,
+    ) -> anyhow::Result<Quote> {
+        self.quote_swap_exact_in_synced_trace(synced, amount)
+            .map(|QuoteTrace { quote, .. }| quote)
+    }
+
+    pub(crate) fn quote_swap_exact_in_synced_trace(
+        &self,
+        synced: &SyncedSwapLsts,
+        amount: u64,
+    ) -> anyhow::Result<QuoteTrace> {
+        self.quote_swap_exact_in_synced_inner(synced, amount, None)
+    }
+
+    fn quote_swap_exact_in_synced_inner(
+        &self,
+        SyncedSwapLsts {
+            pool_state,
+            input_mint,
+            input_lst_data,
+            output_mint,
+            output_lst_data,
+            output_reserves_balance,
+        }: &SyncedSwapLsts,
+        amount: u64,
+        pricing_sol_value_override: Option<u64>,
+    ) -> anyhow::Result<QuoteTrace> {
+        let pricing_prog = self.pricing_prog()?;
 
-        let in_sol_value = input_lst_data.sol_val_calc.lst_to_sol(*amount)?.get_min();
+        let in_sol_value = input_lst_data.sol_val_calc.lst_to_sol(amount)?.get_min();
         if in_sol_value == 0 {
             return Err(SControllerError::ZeroValue.into());
         }
@@ -58,8 +351,8 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
                 output_lst_mint: *output_mint,
             },
             &PriceExactInIxArgs {
-                amount: *amount,
-                sol_value: in_sol_value,
+                amount,
+                sol_value: pricing_sol_value_override.unwrap_or(in_sol_value),
             },
         )?;
         if out_sol_value > in_sol_value {
@@ -78,23 +371,35 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             dst_lst_out,
             trading_protocol_fee_bps: pool_state.trading_protocol_fee_bps,
         })?;
+        // for 1:1 output calcs like WSOL, dst_lst_out + protocol fees <= in_sol_value so this
+        // cannot overflow, but other output calcs can convert to more LST than SOL value
         let total_dst_lst_out = dst_lst_out
             .checked_add(to_protocol_fees_lst_amount)
             .ok_or(SControllerError::MathError)?;
-        let not_enough_liquidity = total_dst_lst_out > output_reserves_balance;
+        let not_enough_liquidity = total_dst_lst_out > *output_reserves_balance;
         let (fee_amount, fee_pct) = calc_quote_fees(
             AmtsAfterFeeBuilder::new_amt_bef_fee(in_sol_value).with_amt_aft_fee(out_sol_value)?,
             &output_lst_data.sol_val_calc,
         )?;
-        Ok(Quote {
+        let quote = Quote {
             not_enough_liquidity,
             min_in_amount: None,
             min_out_amount: None,
-            in_amount: *amount,
+            in_amount: amount,
             out_amount: dst_lst_out,
             fee_mint: *output_mint,
             fee_amount,
             fee_pct,
+        };
+        Ok(QuoteTrace {
+            quote,
+            synced_input_mint: *input_mint,
+            synced_output_mint: *output_mint,
+            synced_total_sol_value: pool_state.total_sol_value,
+            in_sol_value,
+            out_sol_value,
+            dst_lst_out,
+            to_protocol_fees_lst_amount,
         })
     }
 
@@ -123,14 +428,22 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
                 pubkey: *destination_mint,
                 token_program: dst_token_program,
             },
-            lst_state_list: &self.lst_state_list_account,
+            lst_state_list: &*self.lst_state_list_account,
         })
     }
 
+    /// Uses `swap_params.out_amount` as `min_amount_out`
     pub(crate) fn swap_exact_in_ix(&self, swap_params: &SwapParams) -> anyhow::Result<Instruction> {
+        self.swap_exact_in_ix_with_min_out(swap_params, swap_params.out_amount)
+    }
+
+    pub(crate) fn swap_exact_in_ix_with_min_out(
+        &self,
+        swap_params: &SwapParams,
+        min_amount_out: u64,
+    ) -> anyhow::Result<Instruction> {
         let SwapParams {
             in_amount,
-            out_amount,
             source_mint,
             destination_mint,
             ..
@@ -161,8 +474,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             self.program_id,
             self.swap_by_mints_free_args(*src_token_program, *dst_token_program, swap_params)?,
             SwapExactInAmounts {
-                // TODO: where did other_amount_threshold go?
-                min_amount_out: *out_amount,
+                min_amount_out,
                 amount: *in_amount,
             },
             SrcDstLstSolValueCalcAccountSuffixes {
@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use jupiter_amm_interface::{Quote, QuoteParams, SwapAndAccountMetas, SwapParams};
+use jupiter_amm_interface::{Quote, QuoteParams, Swap, SwapAndAccountMetas, SwapMode, SwapParams};
 use pricing_programs_interface::{PriceExactOutIxArgs, PriceExactOutKeys};
 use s_controller_interface::{swap_exact_out_ix, SControllerError, SwapExactOutIxArgs};
 use s_controller_lib::{
@@ -32,12 +32,9 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
     ) -> anyhow::Result<Quote> {
         let pool_state_data = self.pool_state_data()?;
         let pool_state = try_pool_state(&pool_state_data)?;
-        let pricing_prog = self
-            .pricing_prog
-            .as_ref()
-            .ok_or_else(|| anyhow!("pricing program not fetched"))?;
+        let pricing_prog = self.pricing_prog()?;
 
-        let (input_lst_state, input_lst_data) = self.find_ready_lst(*input_mint)?;
+        let (input_lst_state, input_lst_data) = self.find_ready_input_lst(*input_mint)?;
         let (pool_state, _input_lst_state, _input_reserves_balance) =
             apply_sync_sol_value(*pool_state, input_lst_state, input_lst_data)?;
         let (output_lst_state, output_lst_data) = self.find_ready_lst(*output_mint)?;
@@ -94,12 +91,43 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         })
     }
 
+    /// Returns a `max_amount_in` for a SwapExactOut that covers the quoted input amount
+    /// plus the input LST's rounding on converting 1 lamport of SOL value,
+    /// so that the swap does not revert from under-provisioning.
+    ///
+    /// Only ExactOut LST -> LST swaps are supported.
+    pub fn safe_max_amount_in(&self, quote_params: &QuoteParams) -> anyhow::Result<u64> {
+        if quote_params.swap_mode != SwapMode::ExactOut {
+            return Err(anyhow!("safe_max_amount_in only supported for ExactOut"));
+        }
+        let lp_mint = self.lp_token_mint()?;
+        if quote_params.input_mint == lp_mint || quote_params.output_mint == lp_mint {
+            return Err(anyhow!(
+                "safe_max_amount_in not supported for add/remove liquidity"
+            ));
+        }
+        let Quote { in_amount, .. } = self.quote_swap_exact_out(quote_params)?;
+        let (_, input_lst_data) = self.find_ready_lst(quote_params.input_mint)?;
+        let rounding = input_lst_data.sol_val_calc.sol_to_lst(1)?.get_max().max(1);
+        in_amount
+            .checked_add(rounding)
+            .ok_or_else(|| anyhow!("safe_max_amount_in overflow"))
+    }
+
+    /// Uses `swap_params.in_amount` as `max_amount_in`
     pub(crate) fn swap_exact_out_ix(
         &self,
         swap_params: &SwapParams,
+    ) -> anyhow::Result<Instruction> {
+        self.swap_exact_out_ix_with_max_in(swap_params, swap_params.in_amount)
+    }
+
+    pub(crate) fn swap_exact_out_ix_with_max_in(
+        &self,
+        swap_params: &SwapParams,
+        max_amount_in: u64,
     ) -> anyhow::Result<Instruction> {
         let SwapParams {
-            in_amount,
             out_amount,
             source_mint,
             destination_mint,
@@ -131,8 +159,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             self.program_id,
             self.swap_by_mints_free_args(*src_token_program, *dst_token_program, swap_params)?,
             SwapExactOutAmounts {
-                // TODO: where did other_amount_threshold go?
-                max_amount_in: *in_amount,
+                max_amount_in,
                 amount: *out_amount,
             },
             SrcDstLstSolValueCalcAccountSuffixes {
@@ -149,6 +176,37 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         )?)
     }
 
+    /// Returns the SwapExactOut account metas for `swap_params` and the instruction args that
+    /// go with them. `amount` is `swap_params.out_amount` and the slippage threshold
+    /// `max_amount_in` is `swap_params.in_amount`.
+    ///
+    /// Unlike [`Self::get_swap_and_account_metas_full`], this does not need `in_amount` to be
+    /// 0 to select ExactOut. Pass both to [`super::swap_and_account_metas_to_ix`] with
+    /// `SwapExactOutIxData` to build the instruction.
+    pub fn swap_exact_out_account_metas_and_args(
+        &self,
+        swap_params: &SwapParams,
+    ) -> anyhow::Result<(SwapAndAccountMetas, SwapExactOutIxArgs)> {
+        let metas = self.swap_exact_out_swap_and_account_metas(swap_params)?;
+        let args = match metas.swap {
+            Swap::SanctumS {
+                src_lst_value_calc_accs,
+                dst_lst_value_calc_accs,
+                src_lst_index,
+                dst_lst_index,
+            } => SwapExactOutIxArgs {
+                src_lst_value_calc_accs,
+                dst_lst_value_calc_accs,
+                src_lst_index,
+                dst_lst_index,
+                amount: swap_params.out_amount,
+                max_amount_in: swap_params.in_amount,
+            },
+            _ => return Err(anyhow!("Unexpected swap variant for SwapExactOut")),
+        };
+        Ok((metas, args))
+    }
+
     pub(crate) fn swap_exact_out_swap_and_account_metas(
         &self,
         swap_params: &SwapParams,
@@ -237,7 +295,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         )?;
 
         Ok(SwapAndAccountMetas {
-            swap: jupiter_amm_interface::Swap::SanctumS {
+            swap: Swap::SanctumS {
                 src_lst_value_calc_accs,
                 dst_lst_value_calc_accs,
                 src_lst_index: index_to_u32(src_lst_index)?,
@@ -0,0 +1,95 @@
+use jupiter_amm_interface::Quote;
+use pricing_programs_interface::{PriceExactInIxArgs, PriceExactInKeys};
+use s_controller_interface::SControllerError;
+use s_controller_lib::{calc_swap_protocol_fees, try_pool_state, CalcSwapProtocolFeesArgs};
+use s_pricing_prog_aggregate::PricingProg;
+use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc};
+use sanctum_token_ratio::AmtsAfterFeeBuilder;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::SPool;
+
+use super::{apply_sync_sol_value, calc_quote_fees};
+
+/// An output LST that is not on the pool's LST list,
+/// for "what if we added this LST" analysis
+#[derive(Clone, Copy, Debug)]
+pub struct SyntheticOutputLst<'a> {
+    /// Sol value calculator of the synthetic output LST.
+    /// Must already be updated.
+    pub sol_val_calc: &'a KnownLstSolValCalc,
+
+    /// LST on the pool's list whose output fee
+    /// the synthetic output LST is priced with
+    pub pricing_output_mint: Pubkey,
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Quotes a SwapExactIn of `amount` of `input_mint` into a synthetic output LST
+    /// that is not in the pool, converting the output sol value with its provided
+    /// sol value calculator.
+    ///
+    /// Since the output LST has no reserves, `not_enough_liquidity` is always false.
+    pub fn quote_swap_exact_in_synthetic(
+        &self,
+        input_mint: Pubkey,
+        amount: u64,
+        SyntheticOutputLst {
+            sol_val_calc: output_sol_val_calc,
+            pricing_output_mint,
+        }: SyntheticOutputLst,
+    ) -> anyhow::Result<Quote> {
+        let pool_state_data = self.pool_state_data()?;
+        let pool_state = try_pool_state(&pool_state_data)?;
+        let pricing_prog = self.pricing_prog()?;
+
+        let (input_lst_state, input_lst_data) = self.find_ready_input_lst(input_mint)?;
+        let (pool_state, _input_lst_state, _input_reserves_balance) =
+            apply_sync_sol_value(*pool_state, input_lst_state, input_lst_data)?;
+
+        let in_sol_value = input_lst_data.sol_val_calc.lst_to_sol(amount)?.get_min();
+        if in_sol_value == 0 {
+            return Err(SControllerError::ZeroValue.into());
+        }
+        let out_sol_value = pricing_prog.quote_exact_in(
+            PriceExactInKeys {
+                input_lst_mint: input_mint,
+                output_lst_mint: pricing_output_mint,
+            },
+            &PriceExactInIxArgs {
+                amount,
+                sol_value: in_sol_value,
+            },
+        )?;
+        if out_sol_value > in_sol_value {
+            return Err(SControllerError::PoolWouldLoseSolValue.into());
+        }
+        let dst_lst_out = output_sol_val_calc.sol_to_lst(out_sol_value)?.get_min();
+        if dst_lst_out == 0 {
+            return Err(SControllerError::ZeroValue.into());
+        }
+        // protocol fees dont affect out_amount but still
+        // check for overflow like the real swap would
+        calc_swap_protocol_fees(CalcSwapProtocolFeesArgs {
+            in_sol_value,
+            out_sol_value,
+            dst_lst_out,
+            trading_protocol_fee_bps: pool_state.trading_protocol_fee_bps,
+        })?;
+        let (fee_amount, fee_pct) = calc_quote_fees(
+            AmtsAfterFeeBuilder::new_amt_bef_fee(in_sol_value).with_amt_aft_fee(out_sol_value)?,
+            output_sol_val_calc,
+        )?;
+        Ok(Quote {
+            not_enough_liquidity: false,
+            min_in_amount: None,
+            min_out_amount: None,
+            in_amount: amount,
+            out_amount: dst_lst_out,
+            fee_mint: output_sol_val_calc.lst_mint(),
+            fee_amount,
+            fee_pct,
+        })
+    }
+}
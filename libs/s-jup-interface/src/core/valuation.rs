@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use jupiter_amm_interface::{Quote, QuoteParams, SwapMode};
+use rust_decimal::Decimal;
+use s_controller_interface::{LstState, PoolState, SyncSolValueKeys};
+use s_controller_lib::{
+    sync_sol_value_ix_full_for_prog, try_find_lst_mint_on_list, try_lst_state_list, try_pool_state,
+};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_token_lib::token_account_balance;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{LstData, SPool, SPoolError};
+
+use super::{apply_sync_sol_value, reserves_sol_value, QuoteTrace};
+
+const SOL_DECIMALS: u32 = 9;
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Returns the pool state after running SyncSolValue on every supported LST
+    pub(crate) fn pool_state_all_synced(&self) -> anyhow::Result<PoolState> {
+        let pool_state_data = self.pool_state_data()?;
+        let pool_state = try_pool_state(&pool_state_data)?;
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_data)?;
+        lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .filter_map(|(lst_state, lst_data)| lst_data.as_ref().map(|ld| (lst_state, ld)))
+            .try_fold(*pool_state, |pool_state, (lst_state, lst_data)| {
+                apply_sync_sol_value(pool_state, *lst_state, lst_data)
+                    .map(|(pool_state, _lst_state, _reserves_balance)| pool_state)
+            })
+    }
+
+    /// Returns the sum of every LST's freshly computed sol value minus the total SOL value
+    /// recorded in pool state. LSTs that are not supported contribute their recorded sol value.
+    ///
+    /// Nonzero means sol values on chain are out of sync.
+    pub fn sol_value_sync_drift(&self) -> anyhow::Result<i128> {
+        let pool_state_data = self.pool_state_data()?;
+        let pool_state = try_pool_state(&pool_state_data)?;
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_data)?;
+        let computed_total = lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .try_fold(0i128, |sum, (lst_state, lst_data)| {
+                let sol_value = match lst_data {
+                    Some(ld) => {
+                        let (_pool_state, lst_state, _reserves_balance) =
+                            apply_sync_sol_value(*pool_state, *lst_state, ld)?;
+                        lst_state.sol_value
+                    }
+                    None => lst_state.sol_value,
+                };
+                Ok::<_, anyhow::Error>(sum + i128::from(sol_value))
+            })?;
+        Ok(computed_total - i128::from(pool_state.total_sol_value))
+    }
+
+    /// Returns how much [`Self::total_sol_value`] would change if reserves were refetched now:
+    /// the SOL value of supported LSTs' reserves balances in `fresh` minus the SOL value of
+    /// their cached balances. LSTs whose reserves account is not in `fresh` contribute 0.
+    ///
+    /// Only reserves balances are read from `fresh`, sol value calculators are not updated.
+    pub fn potential_sol_value_shift<D: ReadonlyAccountData>(
+        &self,
+        fresh: &HashMap<Pubkey, D>,
+    ) -> anyhow::Result<i128> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_data)?;
+        lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .filter_map(|(lst_state, lst_data)| lst_data.as_ref().map(|ld| (lst_state, ld)))
+            .try_fold(0i128, |shift, (lst_state, lst_data)| {
+                let reserves = self.pool_reserves_account(lst_state, lst_data)?;
+                let fresh_balance = match fresh.get(&reserves) {
+                    Some(acc) => token_account_balance(acc)?,
+                    None => return Ok(shift),
+                };
+                let (_cached_balance, cached_sol_value) = reserves_sol_value(lst_data)?;
+                let fresh_sol_value = lst_data.sol_val_calc.lst_to_sol(fresh_balance)?.get_min();
+                Ok::<_, anyhow::Error>(
+                    shift + i128::from(fresh_sol_value) - i128::from(cached_sol_value),
+                )
+            })
+    }
+
+    /// Returns SyncSolValue instructions for every supported LST whose sol value
+    /// recorded on chain differs from its freshly computed sol value
+    pub fn drift_correction_ixs(&self) -> anyhow::Result<Vec<Instruction>> {
+        let pool_state_data = self.pool_state_data()?;
+        let pool_state = try_pool_state(&pool_state_data)?;
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_data)?;
+        lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .enumerate()
+            .filter_map(|(lst_index, (lst_state, lst_data))| {
+                lst_data.as_ref().map(|ld| (lst_index, lst_state, ld))
+            })
+            .filter_map(|(lst_index, lst_state, lst_data)| {
+                match apply_sync_sol_value(*pool_state, *lst_state, lst_data) {
+                    Ok((_pool_state, synced, _reserves_balance))
+                        if synced.sol_value == lst_state.sol_value =>
+                    {
+                        None
+                    }
+                    Ok(_) => Some(self.sync_sol_value_ix_at(lst_index, lst_state, lst_data)),
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a SyncSolValue instruction for `lst_mint` with its sol value calculator
+    /// program and accounts appended, e.g. for keepers to run before a rebalance.
+    ///
+    /// The LST's index is resolved from the lst_state_list account of this SPool.
+    pub fn sync_sol_value_ix_by_mint(&self, lst_mint: Pubkey) -> anyhow::Result<Instruction> {
+        let (lst_state, lst_data) = self.find_ready_lst(lst_mint)?;
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_data)?;
+        let (lst_index, _lst_state) = try_find_lst_mint_on_list(lst_mint, lst_state_list)?;
+        self.sync_sol_value_ix_at(lst_index, &lst_state, lst_data)
+    }
+
+    /// The program checks `pool_reserves` against the derived reserves ATA,
+    /// so reserves account overrides are not used here
+    fn sync_sol_value_ix_at(
+        &self,
+        lst_index: usize,
+        lst_state: &LstState,
+        lst_data: &LstData,
+    ) -> anyhow::Result<Instruction> {
+        Ok(sync_sol_value_ix_full_for_prog(
+            self.program_id,
+            SyncSolValueKeys {
+                lst_mint: lst_state.mint,
+                pool_state: self.pool_state_addr,
+                lst_state_list: self.lst_state_list_addr,
+                pool_reserves: Self::reserves_ata(
+                    self.pool_state_addr,
+                    lst_state.mint,
+                    lst_data.token_program,
+                    lst_state.pool_reserves_bump,
+                )?,
+            },
+            lst_index,
+            &lst_data.sol_val_calc.ix_accounts(),
+            lst_data.sol_val_calc.sol_value_calculator_program_id(),
+        )?)
+    }
+
+    /// Returns (LST mint, reserves balance) for every LST on the list.
+    ///
+    /// Balance is `None` if the LST is not supported or its reserves have not been fetched.
+    pub fn lst_reserves(&self) -> Vec<(Pubkey, Option<u64>)> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = match try_lst_state_list(&lst_state_list_data) {
+            Ok(l) => l,
+            Err(_) => return vec![],
+        };
+        lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .map(|(lst_state, lst_data)| {
+                (
+                    lst_state.mint,
+                    lst_data.as_ref().and_then(|ld| ld.reserves_balance),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns (LST mint, SOL value of its reserves balance) for every LST on the list,
+    /// computed with its sol value calculator.
+    ///
+    /// SOL value is `None` if the LST is not supported or its reserves have not been fetched.
+    pub fn lst_sol_values(&self) -> anyhow::Result<Vec<(Pubkey, Option<u64>)>> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_data)?;
+        lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .map(|(lst_state, lst_data)| {
+                let sol_value = match lst_data {
+                    Some(ld) if ld.reserves_balance.is_some() => Some(reserves_sol_value(ld)?.1),
+                    _ => None,
+                };
+                Ok((lst_state.mint, sol_value))
+            })
+            .collect()
+    }
+
+    /// Returns the smallest amount of `lst_mint` that its sol value calculator values at
+    /// nonzero SOL. Swapping or adding liquidity with less fails with `ZeroValue`.
+    pub fn min_nonzero_sol_value_amount(&self, lst_mint: Pubkey) -> anyhow::Result<u64> {
+        let (_lst_state, LstData { sol_val_calc, .. }) = self.find_ready_lst(lst_mint)?;
+        let is_nonzero = |amount: u64| -> anyhow::Result<bool> {
+            Ok(sol_val_calc.lst_to_sol(amount)?.get_min() > 0)
+        };
+        // lst_to_sol() is monotonic: double until nonzero, then binary search the last interval.
+        // Invariant: lo is 0 or worth 0 SOL, hi is worth nonzero SOL
+        let mut hi = 1u64;
+        while !is_nonzero(hi)? {
+            hi = hi
+                .checked_mul(2)
+                .ok_or_else(|| anyhow!("No amount of {lst_mint} is worth nonzero SOL"))?;
+        }
+        let mut lo = hi / 2;
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if is_nonzero(mid)? {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        Ok(hi)
+    }
+
+    /// Returns the pool's total SOL value after running SyncSolValue on every supported LST,
+    /// which is what the program uses for LP token calculations
+    pub fn total_sol_value(&self) -> anyhow::Result<u64> {
+        self.pool_state_all_synced().map(
+            |PoolState {
+                 total_sol_value, ..
+             }| total_sol_value,
+        )
+    }
+
+    /// Returns the SOL value of 1 LP token: [`Self::total_sol_value`] / LP token supply.
+    ///
+    /// Errors if LP token supply is zero.
+    pub fn lp_token_price_sol(&self) -> anyhow::Result<Decimal> {
+        let lp_token_supply = self.fresh_lp_mint_supply()?;
+        if lp_token_supply == 0 {
+            return Err(anyhow!("LP token supply is zero, LP token has no price"));
+        }
+        Decimal::from(self.total_sol_value()?)
+            .checked_div(Decimal::from(lp_token_supply))
+            .ok_or_else(|| anyhow!("Decimal LP token price div err"))
+    }
+
+    /// Returns the change in [`Self::lp_token_price_sol`] caused by a SwapExactIn:
+    /// the input and output reserves' SOL values after adding the input amount and removing
+    /// the output and protocol fee amounts, less their SOL values before, divided by LP token supply.
+    ///
+    /// Only ExactIn LST -> LST swaps are supported.
+    pub fn nav_delta_from_swap(
+        &self,
+        QuoteParams {
+            amount,
+            input_mint,
+            output_mint,
+            swap_mode,
+        }: &QuoteParams,
+    ) -> anyhow::Result<Decimal> {
+        if *swap_mode != SwapMode::ExactIn {
+            return Err(anyhow!("NAV delta only supported for ExactIn"));
+        }
+        let lp_token_supply = self.fresh_lp_mint_supply()?;
+        if lp_token_supply == 0 {
+            return Err(anyhow!("LP token supply is zero, LP token has no price"));
+        }
+        let synced = self.sync_swap_lsts(*input_mint, *output_mint)?;
+        let QuoteTrace {
+            quote: Quote { out_amount, .. },
+            to_protocol_fees_lst_amount: protocol_fee,
+            ..
+        } = self.quote_swap_exact_in_synced_trace(&synced, *amount)?;
+        let (input_reserves, input_sol_value) = reserves_sol_value(synced.input_lst_data)?;
+        let (output_reserves, output_sol_value) = reserves_sol_value(synced.output_lst_data)?;
+        let input_reserves_after = input_reserves
+            .checked_add(*amount)
+            .ok_or_else(|| anyhow!("Input reserves overflow"))?;
+        let output_reserves_after = out_amount
+            .checked_add(protocol_fee)
+            .and_then(|out| output_reserves.checked_sub(out))
+            .ok_or(SPoolError::InsufficientLiquidity(*output_mint))?;
+        let input_sol_value_after = synced
+            .input_lst_data
+            .sol_val_calc
+            .lst_to_sol(input_reserves_after)?
+            .get_min();
+        let output_sol_value_after = synced
+            .output_lst_data
+            .sol_val_calc
+            .lst_to_sol(output_reserves_after)?
+            .get_min();
+        let sol_value_delta = i128::from(input_sol_value_after)
+            + i128::from(output_sol_value_after)
+            - i128::from(input_sol_value)
+            - i128::from(output_sol_value);
+        Decimal::try_from_i128_with_scale(sol_value_delta, 0)?
+            .checked_div(Decimal::from(lp_token_supply))
+            .ok_or_else(|| anyhow!("Decimal NAV delta div err"))
+    }
+
+    /// Returns the pool's total SOL value valued at `sol_usd` USD per SOL
+    pub fn tvl_in_usd(&self, sol_usd: Decimal) -> anyhow::Result<Decimal> {
+        let PoolState {
+            total_sol_value, ..
+        } = self.pool_state_all_synced()?;
+        Decimal::from_i128_with_scale(total_sol_value.into(), SOL_DECIMALS)
+            .checked_mul(sol_usd)
+            .ok_or_else(|| anyhow!("Decimal tvl overflow"))
+    }
+}
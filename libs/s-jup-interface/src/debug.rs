@@ -0,0 +1,66 @@
+use s_controller_lib::{try_lst_state_list, try_pool_state};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use serde_json::{json, Value};
+use solana_readonly_account::ReadonlyAccountData;
+
+use crate::SPool;
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Dumps the pool's addresses, decoded pool state, each LST's state and data,
+    /// pricing program kind and LP token supply as JSON, for attaching to bug reports.
+    ///
+    /// Pubkeys are base58 strings and data that has not been fetched or failed to decode is `null`.
+    pub fn to_debug_json(&self) -> Value {
+        let pool_state = self.pool_state_data().ok().and_then(|d| {
+            try_pool_state(&d).ok().map(|ps| {
+                json!({
+                    "total_sol_value": ps.total_sol_value,
+                    "trading_protocol_fee_bps": ps.trading_protocol_fee_bps,
+                    "lp_protocol_fee_bps": ps.lp_protocol_fee_bps,
+                    "version": ps.version,
+                    "is_disabled": ps.is_disabled,
+                    "is_rebalancing": ps.is_rebalancing,
+                    "admin": ps.admin.to_string(),
+                    "rebalance_authority": ps.rebalance_authority.to_string(),
+                    "protocol_fee_beneficiary": ps.protocol_fee_beneficiary.to_string(),
+                    "pricing_program": ps.pricing_program.to_string(),
+                    "lp_token_mint": ps.lp_token_mint.to_string(),
+                })
+            })
+        });
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lsts: Vec<Value> = try_lst_state_list(&lst_state_list_data)
+            .map(|list| {
+                list.iter()
+                    .zip(self.lst_data_list.iter())
+                    .map(|(lst_state, lst_data)| {
+                        json!({
+                            "mint": lst_state.mint.to_string(),
+                            "sol_value": lst_state.sol_value,
+                            "is_input_disabled": lst_state.is_input_disabled,
+                            "sol_value_calculator": lst_state.sol_value_calculator.to_string(),
+                            "data": lst_data.as_ref().map(|ld| json!({
+                                "reserves_balance": ld.reserves_balance,
+                                "token_program": ld.token_program.to_string(),
+                                "sol_value_calculator_program_id":
+                                    ld.sol_val_calc.sol_value_calculator_program_id().to_string(),
+                            })),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        json!({
+            "program_id": self.program_id.to_string(),
+            "pool_state_addr": self.pool_state_addr.to_string(),
+            "lst_state_list_addr": self.lst_state_list_addr.to_string(),
+            "pool_state": pool_state,
+            "lst_count": lsts.len(),
+            "lsts": lsts,
+            "pricing_prog_kind": self.pricing_prog.as_ref().map(|pp| format!("{:?}", pp.kind())),
+            "lp_mint_supply": self.lp_mint_supply,
+            "lp_supply_generation": self.lp_supply_generation,
+            "generation": self.generation,
+        })
+    }
+}
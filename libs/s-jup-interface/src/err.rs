@@ -0,0 +1,126 @@
+use std::{error::Error, fmt::Display};
+
+use s_controller_interface::SControllerError;
+use s_pricing_prog_aggregate::PricingProgErr;
+use solana_sdk::pubkey::Pubkey;
+
+/// Errors returned by [`crate::SPool`]'s account accessors and [`crate::SPool::quote_full`].
+///
+/// Since this implements [`Error`], `anyhow::Error: From<SPoolError>` via anyhow's blanket impl,
+/// so `?` still works in functions returning `anyhow::Result`, and the variant can be
+/// recovered with `anyhow::Error::downcast_ref::<SPoolError>()`.
+///
+/// [`jupiter_amm_interface::Amm::quote`] returns [`Self::into_anyhow`] instead,
+/// so errors from the s controller and pricing program downcast to their own types there.
+#[derive(Debug)]
+pub enum SPoolError {
+    PoolStateNotFetched,
+    PricingProgNotFetched,
+    LpMintNotFetched,
+    LpMintSupplyStale {
+        lp_supply_generation: Option<u64>,
+        generation: u64,
+    },
+    LstNotOnList(Pubkey),
+    LstNotSupported(Pubkey),
+    /// The LST is excluded by [`crate::SPool::set_allowlist`]
+    LstNotAllowed(Pubkey),
+    /// Reserves balance of the LST not fetched
+    ReservesNotFetched(Pubkey),
+    /// The LST has no reserves to output
+    InsufficientLiquidity(Pubkey),
+    /// A built swap would use more accounts than a transaction allows
+    TooManyAccounts {
+        accounts: usize,
+        max: usize,
+    },
+    SController(SControllerError),
+    PricingProg(PricingProgErr),
+    /// Errors from the pricing program, sol value calculators and math that have no variant here
+    Other(anyhow::Error),
+}
+
+impl Display for SPoolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PoolStateNotFetched => f.write_str("Pool state not fetched"),
+            Self::PricingProgNotFetched => f.write_str("pricing program not fetched"),
+            Self::LpMintNotFetched => f.write_str("LP mint not fetched"),
+            Self::LpMintSupplyStale {
+                lp_supply_generation,
+                generation,
+            } => write!(
+                f,
+                "LP mint supply stale: last updated at generation {lp_supply_generation:?}, current generation {generation}"
+            ),
+            Self::LstNotOnList(mint) => write!(f, "LST {mint} not on list"),
+            Self::LstNotSupported(mint) => write!(f, "LST {mint} not supported"),
+            Self::LstNotAllowed(mint) => write!(f, "LST {mint} not on allowlist"),
+            Self::ReservesNotFetched(mint) => write!(f, "Reserves balance of LST {mint} not fetched"),
+            Self::InsufficientLiquidity(mint) => write!(f, "LST {mint} has no reserves to output"),
+            Self::TooManyAccounts { accounts, max } => {
+                write!(f, "Transaction would use {accounts} accounts, max is {max}")
+            }
+            Self::SController(e) => Display::fmt(e, f),
+            Self::PricingProg(e) => Display::fmt(e, f),
+            Self::Other(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for SPoolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SController(e) => Some(e),
+            Self::PricingProg(e) => Some(e),
+            Self::Other(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+impl From<SControllerError> for SPoolError {
+    fn from(value: SControllerError) -> Self {
+        Self::SController(value)
+    }
+}
+
+impl From<PricingProgErr> for SPoolError {
+    fn from(value: PricingProgErr) -> Self {
+        Self::PricingProg(value)
+    }
+}
+
+impl SPoolError {
+    /// Converts into the underlying error for variants that wrap one, so that
+    /// `downcast_ref::<SControllerError>()`, `downcast_ref::<PricingProgErr>()` and
+    /// downcasts to the original type of [`Self::Other`] keep working on the result.
+    ///
+    /// Variants that don't wrap an error are converted as is.
+    pub fn into_anyhow(self) -> anyhow::Error {
+        match self {
+            Self::SController(e) => e.into(),
+            Self::PricingProg(e) => e.into(),
+            Self::Other(e) => e,
+            e => e.into(),
+        }
+    }
+}
+
+/// Recovers the typed error if `value` was created from one, else wraps it in [`SPoolError::Other`]
+impl From<anyhow::Error> for SPoolError {
+    fn from(value: anyhow::Error) -> Self {
+        let value = match value.downcast::<Self>() {
+            Ok(e) => return e,
+            Err(e) => e,
+        };
+        let value = match value.downcast::<SControllerError>() {
+            Ok(e) => return Self::SController(e),
+            Err(e) => e,
+        };
+        match value.downcast::<PricingProgErr>() {
+            Ok(e) => Self::PricingProg(e),
+            Err(e) => Self::Other(e),
+        }
+    }
+}
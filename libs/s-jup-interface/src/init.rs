@@ -1,13 +1,17 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::anyhow;
 use s_controller_lib::{
     find_lst_state_list_address, find_pool_state_address, try_lst_state_list, try_pool_state,
 };
 use sanctum_lst_list::SanctumLst;
 use solana_program::pubkey::Pubkey;
 use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::account::Account;
 
 use crate::{
     utils::{try_lst_data, try_pricing_prog},
-    SPool,
+    SPool, SwapAccrual,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -71,7 +75,7 @@ impl<S, L: ReadonlyAccountData> SPool<S, L> {
             let lst_state_list = try_lst_state_list(&lst_state_list_account_data)?;
             lst_state_list
                 .iter()
-                .map(|lst_state| try_lst_data(lst_list, lst_state))
+                .map(|lst_state| try_lst_data(lst_list, &HashMap::new(), lst_state))
                 .collect()
         };
         Ok(Self {
@@ -80,13 +84,46 @@ impl<S, L: ReadonlyAccountData> SPool<S, L> {
             pool_state_addr,
             pool_state_account: None,
             pricing_prog: None,
+            lazy_pricing_prog: None,
             lp_mint_supply: None,
-            lst_state_list_account,
+            lp_supply_generation: None,
+            generation: 0,
+            lst_state_list_account: Arc::new(lst_state_list_account),
             lst_data_list,
+            lst_list: lst_list.into(),
+            reserves_account_overrides: HashMap::new(),
+            frozen_clock: None,
+            unlisted_spl_lsts: HashMap::new(),
+            on_update: None,
+            swap_accrual: SwapAccrual::default(),
+            allowlist: None,
         })
     }
 }
 
+impl<S> SPool<S, Account> {
+    /// [`Self::from_lst_state_list_account`] for when the lst_state_list account's
+    /// data and owner are available but not an [`Account`]
+    pub fn from_lst_state_list_bytes(
+        program_id: Pubkey,
+        lst_state_list_data: &[u8],
+        owner: Pubkey,
+        lst_list: &[SanctumLst],
+    ) -> anyhow::Result<Self> {
+        if owner != program_id {
+            return Err(anyhow!(
+                "lst_state_list owned by {owner} instead of program {program_id}"
+            ));
+        }
+        let lst_state_list_account = Account {
+            data: lst_state_list_data.to_vec(),
+            owner,
+            ..Default::default()
+        };
+        Self::from_lst_state_list_account(program_id, lst_state_list_account, lst_list)
+    }
+}
+
 impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
     /// `Self`s created from this fn must be update_full() 1 more time before they can be used.
     ///  - this update updates the various sol value calculator programs and pricing program
@@ -106,7 +143,7 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
             try_pricing_prog(pool_state, lst_state_list)?
         };
         let mut res = Self::from_lst_state_list_account(program_id, lst_state_list_acc, lst_list)?;
-        res.pool_state_account = Some(pool_state_acc);
+        res.pool_state_account = Some(Arc::new(pool_state_acc));
         res.pricing_prog = Some(pricing_prog);
         Ok(res)
     }
@@ -10,7 +10,7 @@ use sanctum_lst_list::{
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
-use crate::SPoolJup;
+use crate::{SPoolError, SPoolJup};
 
 pub const LABEL: &str = "Sanctum Infinity";
 
@@ -81,6 +81,7 @@ impl Amm for SPoolJup {
 
     fn quote(&self, quote_params: &QuoteParams) -> anyhow::Result<Quote> {
         self.quote_full(quote_params)
+            .map_err(SPoolError::into_anyhow)
     }
 
     fn get_swap_and_account_metas(
@@ -98,7 +99,9 @@ impl Amm for SPoolJup {
         true
     }
 
-    /// TODO: this is not true for AddLiquidity and RemoveLiquidity
+    /// Only true for LST -> LST swaps, but this has no mints to check.
+    /// Returning false would disable ExactOut for those too, so ExactOut add/remove liquidity
+    /// is left to fail at quote time. See [`crate::SPool::supports_exact_out_for`].
     fn supports_exact_out(&self) -> bool {
         true
     }
@@ -0,0 +1,99 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, OnceLock},
+};
+
+use s_controller_lib::{try_lst_state_list, try_pool_state};
+use s_pricing_prog_aggregate::{KnownPricingProg, MutablePricingProg};
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{utils::try_pricing_prog, SPool, SPoolError};
+
+/// Fetches the given accounts on demand, returning whichever of them exist
+pub type PricingProgFetcher<D> =
+    Arc<dyn Fn(&[Pubkey]) -> anyhow::Result<HashMap<Pubkey, D>> + Send + Sync>;
+
+/// Pricing program that is only initialized and fetched
+/// the first time it's required by a quote.
+///
+/// Clones share the same initialized pricing program.
+pub struct LazyPricingProg<D> {
+    fetcher: PricingProgFetcher<D>,
+    pricing_prog: Arc<OnceLock<KnownPricingProg>>,
+}
+
+impl<D> LazyPricingProg<D> {
+    pub fn new(fetcher: PricingProgFetcher<D>) -> Self {
+        Self {
+            fetcher,
+            pricing_prog: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn get(&self) -> Option<&KnownPricingProg> {
+        self.pricing_prog.get()
+    }
+
+    /// Discards the lazily initialized pricing program, if any,
+    /// so that it is refetched on next use
+    pub fn reset(&mut self) {
+        self.pricing_prog = Arc::new(OnceLock::new());
+    }
+}
+
+impl<D> Clone for LazyPricingProg<D> {
+    fn clone(&self) -> Self {
+        Self {
+            fetcher: self.fetcher.clone(),
+            pricing_prog: self.pricing_prog.clone(),
+        }
+    }
+}
+
+impl<D> Debug for LazyPricingProg<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyPricingProg")
+            .field("pricing_prog", &self.pricing_prog.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, L> SPool<S, L> {
+    /// Sets a fetcher that is used to initialize the pricing program
+    /// on the first quote if it has not been fetched by `update()` by then
+    pub fn with_lazy_pricing_prog(mut self, fetcher: PricingProgFetcher<S>) -> Self {
+        self.lazy_pricing_prog = Some(LazyPricingProg::new(fetcher));
+        self
+    }
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Returns the pricing program fetched by `update()`, falling back to
+    /// initializing it with the lazy pricing program fetcher, if set,
+    /// if `update()` has not fetched all of the pricing program's accounts yet
+    pub fn pricing_prog(&self) -> Result<&KnownPricingProg, SPoolError> {
+        let lazy = match (self.pricing_prog.as_ref(), self.lazy_pricing_prog.as_ref()) {
+            (Some(pp), None) => return Ok(pp),
+            (Some(pp), Some(_)) if pp.missing_accounts().is_empty() => return Ok(pp),
+            (_, Some(lazy)) => lazy,
+            (None, None) => return Err(SPoolError::PricingProgNotFetched),
+        };
+        if let Some(pp) = lazy.get() {
+            return Ok(pp);
+        }
+        let mut pp = {
+            let pool_state_data = self.pool_state_data()?;
+            let pool_state = try_pool_state(&pool_state_data)?;
+            let lst_state_list_acc_data = self.lst_state_list_account.data();
+            let lst_state_list = try_lst_state_list(&lst_state_list_acc_data)?;
+            try_pricing_prog(pool_state, lst_state_list)?
+        };
+        let fetched = (lazy.fetcher)(&pp.get_accounts_to_update())?;
+        pp.update(&fetched)?;
+        // another thread may have won the race, in which case just use theirs
+        let _ = lazy.pricing_prog.set(pp);
+        lazy.get().ok_or(SPoolError::PricingProgNotFetched)
+    }
+}
@@ -1,30 +1,67 @@
-use anyhow::anyhow;
-use s_controller_interface::LstState;
-use s_controller_lib::{try_lst_state_list, try_pool_state};
-use s_pricing_prog_aggregate::KnownPricingProg;
-use s_sol_val_calc_prog_aggregate::KnownLstSolValCalc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use s_controller_interface::{LstState, SControllerError};
+use s_controller_lib::{try_lst_state_list, try_pool_state, U8Bool};
+use s_pricing_prog_aggregate::{KnownPricingProg, PricingProgKind};
+use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc, LstSolValCalcKind};
 use sanctum_associated_token_lib::{CreateAtaAddressArgs, FindAtaAddressArgs};
-use solana_program::pubkey::{Pubkey, PubkeyError};
+use sanctum_lst_list::SanctumLst;
+use serde::{Deserialize, Serialize};
+use solana_program::{
+    clock::Clock,
+    pubkey::{Pubkey, PubkeyError},
+};
 use solana_readonly_account::ReadonlyAccountData;
 use solana_sdk::account::Account;
+use utils::try_lst_data;
 
 pub(crate) mod utils;
 
 mod core;
+mod debug;
+mod err;
 mod init;
 mod jup_interface;
+mod lazy_pricing_prog;
+mod maintenance;
+mod pairs;
+mod snapshot;
 mod update;
+mod validation;
+mod view;
+mod wsol;
 
 pub use core::*;
+pub use err::*;
 pub use init::*;
 pub use jup_interface::*;
+pub use lazy_pricing_prog::*;
+pub use snapshot::*;
 pub use update::*;
+pub use view::*;
+pub use wsol::*;
 
 #[derive(Debug, Clone)]
 pub struct LstData {
     pub sol_val_calc: KnownLstSolValCalc,
     pub reserves_balance: Option<u64>,
     pub token_program: Pubkey,
+    // (reserves_balance, sol value of reserves_balance) computed at the end of the latest update_full().
+    // Reused by quotes until the next update, but only while reserves_balance still matches.
+    pub reserves_sol_value: Option<(u64, u64)>,
+    // slot passed to the latest update_full_at_slot() that fetched the reserves account
+    pub last_updated_slot: Option<u64>,
+}
+
+/// An SPL stake pool LST missing from the [`SanctumLst`] list an [`SPool`] was initialized with,
+/// see [`SPool::add_unlisted_spl_lst`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnlistedSplLst {
+    pub stake_pool_addr: Pubkey,
+    pub token_program: Pubkey,
 }
 
 /// Convenience type alias for jupiter
@@ -36,14 +73,37 @@ pub struct SPool<S, L> {
     pub lst_state_list_addr: Pubkey,
     pub pool_state_addr: Pubkey,
     pub lp_mint_supply: Option<u64>,
-    // pool_state optional since lst_state_list is the KeyedAccount we initialize with
-    pub pool_state_account: Option<S>,
-    pub lst_state_list_account: L,
+    // latest value of `generation` at which lp_mint_supply was known to be current
+    pub lp_supply_generation: Option<u64>,
+    // incremented on every update_full()
+    pub generation: u64,
+    // pool_state optional since lst_state_list is the KeyedAccount we initialize with.
+    // Accounts are only replaced when their data changes on update(),
+    // so they are shared between clones instead of copied.
+    pub pool_state_account: Option<Arc<S>>,
+    pub lst_state_list_account: Arc<L>,
     pub pricing_prog: Option<KnownPricingProg>,
+    // fallback for when pricing_prog has not been fetched by update()
+    pub lazy_pricing_prog: Option<LazyPricingProg<S>>,
     // indices match that of lst_state_list.
     // None means we don't know how to handle the given lst
     // this could be due to incomplete data or unknown LST sol value calculator program
     pub lst_data_list: Vec<Option<LstData>>,
+    // list this SPool was initialized with,
+    // for initializing LSTs added to lst_state_list after initialization
+    pub lst_list: Arc<[SanctumLst]>,
+    // LST mint -> reserves account to use instead of the derived ATA
+    pub reserves_account_overrides: HashMap<Pubkey, Pubkey>,
+    // clock set by set_clock() that sol value calculators use in place of the fetched clock sysvar
+    pub frozen_clock: Option<Clock>,
+    // LST mint -> SPL stake pool of LSTs not in lst_list
+    pub unlisted_spl_lsts: HashMap<Pubkey, UnlistedSplLst>,
+    // called at the end of every successful update_full()
+    pub on_update: Option<OnUpdateCallback>,
+    // totals of swaps passed to record_swap()
+    pub swap_accrual: SwapAccrual,
+    // set by set_allowlist(), None means all LSTs are allowed
+    pub allowlist: Option<HashSet<Pubkey>>,
 }
 
 impl<S, L: Default> Default for SPool<S, L> {
@@ -53,10 +113,20 @@ impl<S, L: Default> Default for SPool<S, L> {
             lst_state_list_addr: s_controller_lib::program::LST_STATE_LIST_ID,
             pool_state_addr: s_controller_lib::program::POOL_STATE_ID,
             lp_mint_supply: None,
+            lp_supply_generation: None,
+            generation: 0,
             pool_state_account: None,
             pricing_prog: None,
-            lst_state_list_account: L::default(),
+            lazy_pricing_prog: None,
+            lst_state_list_account: Arc::default(),
             lst_data_list: Vec::new(),
+            lst_list: Vec::new().into(),
+            reserves_account_overrides: HashMap::new(),
+            frozen_clock: None,
+            unlisted_spl_lsts: HashMap::new(),
+            on_update: None,
+            swap_accrual: SwapAccrual::default(),
+            allowlist: None,
         }
     }
 }
@@ -64,12 +134,8 @@ impl<S, L: Default> Default for SPool<S, L> {
 // More impl blocks in other files
 
 impl<S, L> SPool<S, L> {
-    pub fn pricing_prog(&self) -> anyhow::Result<&KnownPricingProg> {
-        self.pricing_prog
-            .as_ref()
-            .ok_or_else(|| anyhow!("pricing program not fetched"))
-    }
-
+    /// Returns the reserves account override for the LST if set with
+    /// [`Self::set_reserves_account`], else the LST's reserves ATA
     pub fn pool_reserves_account(
         &self,
         LstState {
@@ -78,49 +144,177 @@ impl<S, L> SPool<S, L> {
             ..
         }: &LstState,
         LstData { token_program, .. }: &LstData,
+    ) -> Result<Pubkey, PubkeyError> {
+        if let Some(overridden) = self.reserves_account_overrides.get(mint) {
+            return Ok(*overridden);
+        }
+        Self::reserves_ata(
+            self.pool_state_addr,
+            *mint,
+            *token_program,
+            *pool_reserves_bump,
+        )
+    }
+
+    /// Derives an LST's reserves ATA from its `LstState::pool_reserves_bump`
+    /// without requiring a constructed pool. Does not consider reserves account overrides.
+    pub fn reserves_ata(
+        pool_state_addr: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+        bump: u8,
     ) -> Result<Pubkey, PubkeyError> {
         CreateAtaAddressArgs {
             find_ata_args: FindAtaAddressArgs {
-                wallet: self.pool_state_addr,
-                mint: *mint,
-                token_program: *token_program,
+                wallet: pool_state_addr,
+                mint,
+                token_program,
             },
-            bump: *pool_reserves_bump,
+            bump,
         }
         .create_ata_address()
     }
+
+    /// Fetch and read the reserves balance of `lst_mint` from `reserves_account`
+    /// instead of its derived reserves ATA, for deployments where they differ.
+    ///
+    /// Instructions built by this SPool still pass the derived reserves ATA,
+    /// since that is what the program checks against.
+    pub fn set_reserves_account(&mut self, lst_mint: Pubkey, reserves_account: Pubkey) {
+        self.reserves_account_overrides
+            .insert(lst_mint, reserves_account);
+    }
+
+    /// Quote with `clock` instead of the clock sysvar fetched by `update()`,
+    /// e.g. for reproducible quotes in backtests.
+    ///
+    /// Only affects sol value calculators that check the current epoch.
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.lst_data_list.iter_mut().flatten().for_each(|ld| {
+            ld.sol_val_calc.set_clock(clock.clone());
+            // cached values may have been computed with a different clock
+            ld.reserves_sol_value = None;
+        });
+        self.frozen_clock = Some(clock);
+    }
+
+    /// Restrict [`Self::find_ready_lst`], and therefore quotes and swaps, to LSTs in `mints`.
+    /// Other LSTs error with [`SPoolError::LstNotAllowed`] even if supported.
+    ///
+    /// `None` allows all LSTs again.
+    pub fn set_allowlist(&mut self, mints: Option<&[Pubkey]>) {
+        self.allowlist = mints.map(|mints| mints.iter().copied().collect());
+    }
+
+    /// Groups the mints of supported LSTs by the kind of their sol value calculator
+    pub fn lsts_by_calc_variant(&self) -> HashMap<LstSolValCalcKind, Vec<Pubkey>> {
+        self.lst_data_list.iter().flatten().fold(
+            HashMap::new(),
+            |mut res, LstData { sol_val_calc, .. }| {
+                res.entry(sol_val_calc.kind())
+                    .or_default()
+                    .push(sol_val_calc.lst_mint());
+                res
+            },
+        )
+    }
 }
 
 impl<S: ReadonlyAccountData, L> SPool<S, L> {
     // cant return &PoolState directly
     // due to lifetime of pool_state.data()
-    pub fn pool_state_data(&self) -> anyhow::Result<S::DataDeref<'_>> {
+    pub fn pool_state_data(&self) -> Result<S::DataDeref<'_>, SPoolError> {
         let pool_state = self
             .pool_state_account
             .as_ref()
-            .ok_or_else(|| anyhow!("Pool state not fetched"))?;
+            .ok_or(SPoolError::PoolStateNotFetched)?;
         Ok(pool_state.data())
     }
 
-    pub fn lp_token_mint(&self) -> anyhow::Result<Pubkey> {
+    pub fn lp_token_mint(&self) -> Result<Pubkey, SPoolError> {
         let pool_state_acc_data = self.pool_state_data()?;
         Ok(try_pool_state(&pool_state_acc_data).map(|ps| ps.lp_token_mint)?)
     }
+
+    /// Kind of the pool's pricing program, read from pool_state without fetching the pricing program
+    pub fn pricing_prog_kind(&self) -> Result<PricingProgKind, SPoolError> {
+        let pool_state_acc_data = self.pool_state_data()?;
+        let pool_state = try_pool_state(&pool_state_acc_data)?;
+        Ok(PricingProgKind::try_from_program_id(
+            pool_state.pricing_program,
+        )?)
+    }
 }
 
 impl<S, L: ReadonlyAccountData> SPool<S, L> {
-    pub fn find_ready_lst(&self, lst_mint: Pubkey) -> anyhow::Result<(LstState, &LstData)> {
+    pub fn find_ready_lst(&self, lst_mint: Pubkey) -> Result<(LstState, &LstData), SPoolError> {
+        if self
+            .allowlist
+            .as_ref()
+            .is_some_and(|allowlist| !allowlist.contains(&lst_mint))
+        {
+            return Err(SPoolError::LstNotAllowed(lst_mint));
+        }
         let lst_state_list_account_data = self.lst_state_list_account.data();
         let lst_state_list = try_lst_state_list(&lst_state_list_account_data)?;
         let (lst_state, lst_data) = lst_state_list
             .iter()
             .zip(self.lst_data_list.iter())
             .find(|(state, _data)| state.mint == lst_mint)
-            .ok_or_else(|| anyhow!("LST {lst_mint} not on list"))?;
+            .ok_or(SPoolError::LstNotOnList(lst_mint))?;
         let lst_data = lst_data
             .as_ref()
-            .ok_or_else(|| anyhow!("LST {lst_mint} not supported"))?;
+            .ok_or(SPoolError::LstNotSupported(lst_mint))?;
         // need to copy lst_state out due to lifetime of lst_state_list_account_data
         Ok((*lst_state, lst_data))
     }
+
+    /// [`Self::find_ready_lst`] for the input of a quote, i.e. the LST being swapped in or
+    /// added as liquidity. Errors with [`SControllerError::LstInputDisabled`] if the pool has
+    /// disabled input for the LST, since the swap would fail onchain.
+    pub fn find_ready_input_lst(
+        &self,
+        lst_mint: Pubkey,
+    ) -> Result<(LstState, &LstData), SPoolError> {
+        let (lst_state, lst_data) = self.find_ready_lst(lst_mint)?;
+        if U8Bool(lst_state.is_input_disabled).is_true() {
+            return Err(SControllerError::LstInputDisabled.into());
+        }
+        Ok((lst_state, lst_data))
+    }
+
+    /// Returns the sol value calculator of `lst_mint` for converting between it and SOL
+    /// outside of the pool, e.g. with [`LstSolValCalc::lst_to_sol`] and [`LstSolValCalc::sol_to_lst`].
+    ///
+    /// `None` if the LST is not on the list or not supported.
+    /// Unlike [`Self::find_ready_lst`], not restricted by [`Self::set_allowlist`].
+    pub fn sol_val_calc_for(&self, lst_mint: Pubkey) -> Option<&KnownLstSolValCalc> {
+        self.lst_data_list
+            .iter()
+            .flatten()
+            .map(|LstData { sol_val_calc, .. }| sol_val_calc)
+            .find(|sol_val_calc| sol_val_calc.lst_mint() == lst_mint)
+    }
+
+    /// Support an SPL stake pool LST that is not in the `lst_list` this SPool was initialized with.
+    ///
+    /// The sol value calculator program is read from the LST's `LstState` and must be one of the
+    /// SPL sol value calculator programs, else the LST remains unsupported.
+    /// Applies to the LST if it is already on lst_state_list, or if it gets added later.
+    /// Its stake pool must then be fetched with another `update()`.
+    pub fn add_unlisted_spl_lst(&mut self, lst_mint: Pubkey, lst: UnlistedSplLst) {
+        self.unlisted_spl_lsts.insert(lst_mint, lst);
+        let lst_state_list_account_data = self.lst_state_list_account.data();
+        let lst_state_list = match try_lst_state_list(&lst_state_list_account_data) {
+            Ok(l) => l,
+            Err(_e) => return,
+        };
+        lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter_mut())
+            .filter(|(lst_state, lst_data)| lst_state.mint == lst_mint && lst_data.is_none())
+            .for_each(|(lst_state, lst_data)| {
+                *lst_data = try_lst_data(&self.lst_list, &self.unlisted_spl_lsts, lst_state);
+            });
+    }
 }
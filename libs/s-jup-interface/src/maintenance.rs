@@ -0,0 +1,72 @@
+use anyhow::anyhow;
+use s_controller_interface::LstState;
+use s_controller_lib::try_lst_state_list;
+use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc};
+use solana_program::program_pack::Pack;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::{pubkey::Pubkey, rent::Rent};
+
+use crate::SPool;
+
+/// The associated token program always initializes token-2022 ATAs with
+/// the ImmutableOwner extension: account type byte + TLV header, no data
+const IMMUTABLE_OWNER_EXT_LEN: usize = 1 + 2 + 2;
+
+/// Whether the calculator reads stake pool state that must be updated every epoch
+/// to give accurate sol values
+fn is_epoch_sensitive(calc: &KnownLstSolValCalc) -> bool {
+    match calc {
+        KnownLstSolValCalc::Lido(_)
+        | KnownLstSolValCalc::Marinade(_)
+        | KnownLstSolValCalc::Spl(_)
+        | KnownLstSolValCalc::SanctumSpl(_)
+        | KnownLstSolValCalc::SanctumSplMulti(_) => true,
+        KnownLstSolValCalc::Wsol(_) => false,
+    }
+}
+
+impl<S, L> SPool<S, L> {
+    /// Returns the mints of supported LSTs whose sol value calculators depend on
+    /// stake pool state that must be cranked every epoch, e.g. for maintenance bots
+    pub fn lsts_needing_epoch_update(&self) -> Vec<Pubkey> {
+        self.lst_data_list
+            .iter()
+            .flatten()
+            .filter(|ld| is_epoch_sensitive(&ld.sol_val_calc))
+            .map(|ld| ld.sol_val_calc.lst_mint())
+            .collect()
+    }
+
+    /// Returns the rent-exempt minimum lamports, at default rent parameters,
+    /// for funding a new reserves ATA of `token_program` when adding an LST.
+    ///
+    /// Errors if `token_program` is neither spl-token nor token-2022.
+    pub fn reserves_ata_rent(&self, token_program: Pubkey) -> anyhow::Result<u64> {
+        let len = match token_program {
+            spl_token::ID => spl_token::state::Account::LEN,
+            spl_token_2022::ID => spl_token_2022::state::Account::LEN + IMMUTABLE_OWNER_EXT_LEN,
+            _ => return Err(anyhow!("{token_program} is not a token program")),
+        };
+        Ok(Rent::default().minimum_balance(len))
+    }
+}
+
+impl<S, L: ReadonlyAccountData> SPool<S, L> {
+    /// Returns the mints of LSTs on the list whose sol value calculator is still the zero pubkey,
+    /// i.e. added to the pool but pending configuration with SetSolValueCalculator.
+    ///
+    /// Like LSTs with an unknown sol value calculator program, these are not supported,
+    /// but are reported separately since they are expected to be configured.
+    pub fn pending_config_lsts(&self) -> Vec<Pubkey> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = match try_lst_state_list(&lst_state_list_data) {
+            Ok(l) => l,
+            Err(_) => return vec![],
+        };
+        lst_state_list
+            .iter()
+            .filter(|lst_state| lst_state.sol_value_calculator == Pubkey::default())
+            .map(|LstState { mint, .. }| *mint)
+            .collect()
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use s_controller_interface::{LstState, SControllerError};
+use s_controller_lib::{try_lst_state_list, U8Bool};
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{LstData, QuoteTrace, SPool, SPoolError};
+
+fn has_output_liquidity(
+    LstData {
+        reserves_balance, ..
+    }: &LstData,
+) -> bool {
+    matches!(reserves_balance, Some(b) if *b > 0)
+}
+
+impl<S, L: ReadonlyAccountData> SPool<S, L> {
+    /// Returns (input_mint, output_mint) of all LST -> LST swaps that can be quoted.
+    ///
+    /// LSTs whose reserves are not yet fetched are excluded, LSTs whose reserves are empty
+    /// only appear as inputs and input-disabled LSTs only appear as outputs.
+    pub fn swappable_pairs(&self) -> Vec<(Pubkey, Pubkey)> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = match try_lst_state_list(&lst_state_list_data) {
+            Ok(l) => l,
+            Err(_) => return vec![],
+        };
+        let ready: Vec<(&LstState, &LstData)> = lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .filter_map(|(lst_state, lst_data)| lst_data.as_ref().map(|ld| (lst_state, ld)))
+            // both sides of a swap run SyncSolValue, which reads the reserves
+            .filter(|(_, ld)| ld.reserves_balance.is_some())
+            .collect();
+        ready
+            .iter()
+            .filter(|(input, _)| !U8Bool(input.is_input_disabled).is_true())
+            .flat_map(|(input, _)| {
+                ready
+                    .iter()
+                    .filter(move |(output, output_data)| {
+                        output.mint != input.mint && has_output_liquidity(output_data)
+                    })
+                    .map(move |(output, _)| (input.mint, output.mint))
+            })
+            .collect()
+    }
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Returns the max amount of `output_mint` that a swap from `input_mint` can output to the user.
+    ///
+    /// The swap also transfers the trading protocol fee out of the output LST's reserves,
+    /// so this is the reserves balance less the protocol fee's share of it.
+    /// That share is taken from a SwapExactIn quote sized to output about the whole reserves,
+    /// so the result may be off by rounding.
+    ///
+    /// Errors if the output LST has no reserves to output.
+    pub fn max_output(&self, input_mint: Pubkey, output_mint: Pubkey) -> Result<u64, SPoolError> {
+        let (_lst_state, output_lst_data) = self.find_ready_lst(output_mint)?;
+        let reserves = match output_lst_data.reserves_balance {
+            None => return Err(SPoolError::ReservesNotFetched(output_mint)),
+            Some(0) => return Err(SPoolError::InsufficientLiquidity(output_mint)),
+            Some(b) => b,
+        };
+        let synced = self.sync_swap_lsts(input_mint, output_mint)?;
+        let reserves_sol_value = output_lst_data.sol_val_calc.lst_to_sol(reserves)?.get_min();
+        let amount = synced
+            .input_lst_data
+            .sol_val_calc
+            .sol_to_lst(reserves_sol_value)?
+            .get_max();
+        let QuoteTrace {
+            dst_lst_out,
+            to_protocol_fees_lst_amount,
+            ..
+        } = self.quote_swap_exact_in_synced_trace(&synced, amount)?;
+        let total_dst_lst_out = u128::from(dst_lst_out) + u128::from(to_protocol_fees_lst_amount);
+        let max = u128::from(reserves) * u128::from(dst_lst_out) / total_dst_lst_out;
+        Ok(u64::try_from(max).map_err(|_e| SControllerError::MathError)?)
+    }
+
+    /// Returns each output LST's max output, i.e. the smallest [`Self::max_output`]
+    /// over all inputs it can be swapped from in [`Self::swappable_pairs`], omitting pairs that error.
+    pub fn output_liquidity_map(&self) -> HashMap<Pubkey, u64> {
+        self.output_liquidity_map_by_pair().into_iter().fold(
+            HashMap::new(),
+            |mut res, ((_input_mint, output_mint), max)| {
+                res.entry(output_mint)
+                    .and_modify(|curr: &mut u64| *curr = (*curr).min(max))
+                    .or_insert(max);
+                res
+            },
+        )
+    }
+
+    /// Returns [`Self::max_output`] for every pair in [`Self::swappable_pairs`],
+    /// omitting pairs that error.
+    pub fn output_liquidity_map_by_pair(&self) -> HashMap<(Pubkey, Pubkey), u64> {
+        self.swappable_pairs()
+            .into_iter()
+            .filter_map(|(input_mint, output_mint)| {
+                self.max_output(input_mint, output_mint)
+                    .ok()
+                    .map(|max| ((input_mint, output_mint), max))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::anyhow;
+use s_controller_lib::try_lst_state_list;
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_lst_list::SanctumLst;
+use serde::{Deserialize, Serialize};
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::{SPool, SPoolError, SPoolInitAccounts, SPoolJup, UnlistedSplLst};
+
+/// Serializable state of a [`SPoolJup`] for rehydrating it after a restart
+/// without refetching pool state, lst_state_list, reserves and LP mint.
+///
+/// Sol value calculators' and the pricing program's accounts are not captured,
+/// so a rehydrated pool must still fetch them with `update()` before quoting.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SPoolSnapshot {
+    pub program_id: Pubkey,
+    pub lst_state_list_account: Account,
+    pub pool_state_account: Option<Account>,
+    pub lp_mint_supply: Option<u64>,
+    /// Same order as lst_state_list
+    pub lsts: Vec<LstSnapshot>,
+    /// LSTs registered with [`SPool::add_unlisted_spl_lst`]
+    #[serde(default)]
+    pub unlisted_spl_lsts: Vec<(Pubkey, UnlistedSplLst)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LstSnapshot {
+    pub mint: Pubkey,
+    /// Program ID of the sol value calculator in use,
+    /// `None` if the LST was not supported
+    pub sol_value_calculator: Option<Pubkey>,
+    pub reserves_balance: Option<u64>,
+}
+
+impl SPoolJup {
+    pub fn to_snapshot(&self) -> anyhow::Result<SPoolSnapshot> {
+        let lst_state_list_data = &self.lst_state_list_account.data;
+        let lst_state_list = try_lst_state_list(lst_state_list_data)?;
+        let lsts = lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .map(|(lst_state, lst_data)| LstSnapshot {
+                mint: lst_state.mint,
+                sol_value_calculator: lst_data
+                    .as_ref()
+                    .map(|ld| ld.sol_val_calc.sol_value_calculator_program_id()),
+                reserves_balance: lst_data.as_ref().and_then(|ld| ld.reserves_balance),
+            })
+            .collect();
+        Ok(SPoolSnapshot {
+            program_id: self.program_id,
+            lst_state_list_account: Account::clone(&self.lst_state_list_account),
+            pool_state_account: self.pool_state_account.as_deref().cloned(),
+            lp_mint_supply: self.lp_mint_supply,
+            lsts,
+            unlisted_spl_lsts: self
+                .unlisted_spl_lsts
+                .iter()
+                .map(|(mint, lst)| (*mint, *lst))
+                .collect(),
+        })
+    }
+
+    /// Reconstructs the sol value calculators from `lst_list` and the snapshot's unlisted SPL LSTs,
+    /// and restores the snapshot's reserves balances and LP mint supply,
+    /// which are treated as fresh until the next `update()`.
+    ///
+    /// Errors if the calculators reconstructed from the snapshot's lst_state_list
+    /// do not match the ones the snapshot was taken with, e.g. because `lst_list` changed.
+    pub fn from_snapshot(
+        SPoolSnapshot {
+            program_id,
+            lst_state_list_account,
+            pool_state_account,
+            lp_mint_supply,
+            lsts,
+            unlisted_spl_lsts,
+        }: SPoolSnapshot,
+        lst_list: &[SanctumLst],
+    ) -> anyhow::Result<Self> {
+        let mut res = match pool_state_account {
+            Some(pool_state) => Self::from_init_accounts(
+                program_id,
+                SPoolInitAccounts {
+                    lst_state_list: lst_state_list_account,
+                    pool_state,
+                },
+                lst_list,
+            )?,
+            None => {
+                Self::from_lst_state_list_account(program_id, lst_state_list_account, lst_list)?
+            }
+        };
+        for (lst_mint, lst) in unlisted_spl_lsts {
+            res.add_unlisted_spl_lst(lst_mint, lst);
+        }
+        {
+            let lst_state_list = try_lst_state_list(&res.lst_state_list_account.data)?;
+            if lst_state_list.len() != lsts.len() {
+                return Err(anyhow!(
+                    "Snapshot has {} LSTs but its lst_state_list has {}",
+                    lsts.len(),
+                    lst_state_list.len()
+                ));
+            }
+            let mismatch = lst_state_list
+                .iter()
+                .zip(res.lst_data_list.iter())
+                .zip(lsts.iter())
+                .find(|((lst_state, lst_data), lst_snapshot)| {
+                    lst_state.mint != lst_snapshot.mint
+                        || lst_data
+                            .as_ref()
+                            .map(|ld| ld.sol_val_calc.sol_value_calculator_program_id())
+                            != lst_snapshot.sol_value_calculator
+                });
+            if let Some((_, lst_snapshot)) = mismatch {
+                return Err(anyhow!(
+                    "Snapshot sol value calculator of LST {} does not match lst_state_list",
+                    lst_snapshot.mint
+                ));
+            }
+        }
+        res.lst_data_list
+            .iter_mut()
+            .zip(lsts.iter())
+            .for_each(|(lst_data, lst_snapshot)| {
+                if let Some(ld) = lst_data.as_mut() {
+                    ld.reserves_balance = lst_snapshot.reserves_balance;
+                }
+            });
+        if lp_mint_supply.is_some() {
+            res.lp_mint_supply = lp_mint_supply;
+            res.lp_supply_generation = Some(res.generation);
+        }
+        Ok(res)
+    }
+}
+
+impl<S, L: ReadonlyAccountData> SPool<S, L> {
+    /// Returns (LST mint, reserves balance) for every supported LST whose reserves have been
+    /// fetched, in lst_state_list order.
+    ///
+    /// Much smaller than [`SPoolSnapshot`], for a fetcher to ship reserves changes to quoters
+    /// that apply them with [`Self::apply_reserves_snapshot`].
+    pub fn reserves_snapshot(&self) -> Vec<(Pubkey, u64)> {
+        self.lst_data_list
+            .iter()
+            .flatten()
+            .filter_map(|ld| Some((ld.sol_val_calc.lst_mint(), ld.reserves_balance?)))
+            .collect()
+    }
+
+    /// Sets the reserves balances of LSTs to those in `snapshot`,
+    /// e.g. one returned by [`Self::reserves_snapshot`] of another SPool.
+    /// LSTs not in `snapshot` are unchanged.
+    ///
+    /// Errors without applying anything if an LST in `snapshot` is not on the list or not supported.
+    pub fn apply_reserves_snapshot(&mut self, snapshot: &[(Pubkey, u64)]) -> anyhow::Result<()> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_data)?;
+        let indices = snapshot
+            .iter()
+            .map(|(mint, balance)| {
+                let i = lst_state_list
+                    .iter()
+                    .position(|lst_state| lst_state.mint == *mint)
+                    .ok_or(SPoolError::LstNotOnList(*mint))?;
+                match self.lst_data_list.get(i) {
+                    Some(Some(_)) => Ok((i, *balance)),
+                    _ => Err(SPoolError::LstNotSupported(*mint)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        indices.into_iter().for_each(|(i, balance)| {
+            if let Some(Some(ld)) = self.lst_data_list.get_mut(i) {
+                ld.reserves_balance = Some(balance);
+            }
+        });
+        Ok(())
+    }
+}
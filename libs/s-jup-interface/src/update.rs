@@ -1,16 +1,54 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+};
+
+use anyhow::anyhow;
+use jupiter_amm_interface::QuoteParams;
 
 use s_controller_interface::LstState;
 use s_controller_lib::{try_lst_state_list, try_pool_state};
 use s_pricing_prog_aggregate::MutablePricingProg;
 use s_sol_val_calc_prog_aggregate::{LstSolValCalc, MutableLstSolValCalc};
 use sanctum_token_lib::{mint_supply, token_account_balance};
-use solana_readonly_account::ReadonlyAccountData;
+use solana_readonly_account::{ReadonlyAccountData, ReadonlyAccountOwner};
 use solana_sdk::pubkey::Pubkey;
 
-use crate::{utils::try_pricing_prog, LstData, SPool};
+use crate::{
+    utils::{try_lst_data, try_pricing_prog},
+    LstData, SPool, SPoolError,
+};
+
+/// Passed to the callback set with [`SPool::set_on_update`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UpdateSummary {
+    /// [`SPool::generation`] after the update
+    pub generation: u64,
+    /// Mints of LSTs whose reserves balance changed, in lst_state_list order
+    pub changed_reserves: Vec<Pubkey>,
+}
+
+#[derive(Clone)]
+pub struct OnUpdateCallback(pub Arc<dyn Fn(&UpdateSummary) + Send + Sync>);
+
+impl Debug for OnUpdateCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OnUpdateCallback")
+    }
+}
+
+impl<S, L> SPool<S, L> {
+    /// Calls `cb` at the end of every successful [`Self::update_full`],
+    /// e.g. to republish quotes. Replaces the previously set callback.
+    pub fn set_on_update(&mut self, cb: Box<dyn Fn(&UpdateSummary) + Send + Sync>) {
+        self.on_update = Some(OnUpdateCallback(cb.into()));
+    }
+}
 
 impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Returns every account `update_full()` reads, without duplicates, in first-seen order.
+    /// Calculators can share accounts, e.g. the clock sysvar or a common stake pool.
     pub fn get_accounts_to_update_full(&self) -> Vec<Pubkey> {
         let mut res: Vec<Pubkey> = self
             .get_accounts_to_update_base()
@@ -21,11 +59,87 @@ impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
         if let Ok(lp_token_mint) = self.lp_token_mint() {
             res.push(lp_token_mint)
         }
+        let mut seen = HashSet::with_capacity(res.len());
+        res.retain(|pk| seen.insert(*pk));
         res
     }
+
+    /// [`Self::get_accounts_to_update_full`] split into (static, derived):
+    /// - static: known from construction, i.e. the lst_state_list and pool state accounts
+    /// - derived: only known from the data of fetched accounts, e.g. LST reserves,
+    ///   sol value calculator and pricing program accounts, and the LP token mint
+    ///
+    /// Derived accounts may change after an `update()` with the static accounts,
+    /// so integrators can fetch the static accounts first.
+    pub fn get_accounts_to_update_classified(&self) -> (Vec<Pubkey>, Vec<Pubkey>) {
+        let static_accounts = self.get_accounts_to_update_base();
+        let derived = self
+            .get_accounts_to_update_full()
+            .into_iter()
+            .filter(|pk| !static_accounts.contains(pk))
+            .collect();
+        (static_accounts.into(), derived)
+    }
+
+    /// Returns only the entries of `account_map` that quoting `quote_params` reads:
+    /// lst_state_list, pool state, the sol value calculator and reserves accounts of the
+    /// swapped LSTs, the pricing program accounts for the swap and, for add/remove liquidity,
+    /// the LP token mint.
+    ///
+    /// A new SPool updated with just these accounts quotes `quote_params` the same as this one,
+    /// e.g. for saving a small fixture that reproduces a quote. SPool only keeps parsed state,
+    /// so `account_map` must be the accounts this SPool was updated with.
+    /// Errors if any required account is missing from it.
+    pub fn minimal_account_map_for<D: Clone>(
+        &self,
+        quote_params: &QuoteParams,
+        account_map: &HashMap<Pubkey, D>,
+    ) -> anyhow::Result<HashMap<Pubkey, D>> {
+        self.quote_accounts_for(quote_params)?
+            .into_iter()
+            .map(|pk| {
+                account_map
+                    .get(&pk)
+                    .map(|acc| (pk, acc.clone()))
+                    .ok_or_else(|| anyhow!("Account {pk} required to quote missing"))
+            })
+            .collect()
+    }
+
+    fn quote_accounts_for(
+        &self,
+        QuoteParams {
+            input_mint,
+            output_mint,
+            ..
+        }: &QuoteParams,
+    ) -> anyhow::Result<Vec<Pubkey>> {
+        let lp_token_mint = self.lp_token_mint()?;
+        let lst_mints: Vec<Pubkey> = [*input_mint, *output_mint]
+            .into_iter()
+            .filter(|mint| *mint != lp_token_mint)
+            .collect();
+        let mut res = Vec::from(self.get_accounts_to_update_base());
+        if lst_mints.len() < 2 {
+            res.push(lp_token_mint);
+            res.extend(self.get_accounts_to_update_pricing_prog_for_liquidity());
+        } else {
+            res.extend(
+                self.get_accounts_to_update_pricing_prog_for_lsts(lst_mints.iter().copied()),
+            );
+        }
+        for mint in lst_mints {
+            let (lst_state, lst_data) = self.find_ready_lst(mint)?;
+            res.extend(lst_data.sol_val_calc.get_accounts_to_update());
+            res.push(self.pool_reserves_account(&lst_state, lst_data)?);
+        }
+        let mut seen = HashSet::with_capacity(res.len());
+        res.retain(|pk| seen.insert(*pk));
+        Ok(res)
+    }
 }
 
-impl<D: ReadonlyAccountData + Clone> SPool<D, D> {
+impl<D: ReadonlyAccountData + ReadonlyAccountOwner + Clone> SPool<D, D> {
     pub fn update_full(&mut self, account_map: &HashMap<Pubkey, D>) -> anyhow::Result<()> {
         // returns the first encountered error, but tries to update everything eagerly
         // even after encountering an error
@@ -37,11 +151,84 @@ impl<D: ReadonlyAccountData + Clone> SPool<D, D> {
         //  - update lst_state_list before pool_state so we can use the new lst_state_list to reinitialize pricing program if required
         //
         // finally, update LP token supply using the newest pool state
-        self.update_lst_data_list(account_map)
+        //
+        // lastly, cache each LST's reserves sol value for quotes to reuse until the next update
+        let is_lp_supply_fresh = self.lp_supply_generation == Some(self.generation);
+        let pool_state_before = self.pool_state_account.clone();
+        self.generation = self.generation.wrapping_add(1);
+        // only snapshot reserves if someone is listening
+        let reserves_before = self.on_update.as_ref().map(|_| self.lst_reserves());
+        let res = self
+            .update_lst_data_list(account_map)
             .and(self.update_pricing_prog(account_map))
             .and(self.update_lst_state_list(account_map))
             .and(self.update_pool_state(account_map))
-            .and(self.update_lp_token_supply(account_map))
+            .and(self.update_lp_token_supply(account_map));
+        // LP supply only moves together with pool_state (add/remove liquidity),
+        // so a supply that was fresh stays fresh across updates that did not change pool_state
+        let is_pool_state_unchanged = match (&pool_state_before, &self.pool_state_account) {
+            (None, None) => true,
+            (Some(before), Some(after)) => before.data()[..] == after.data()[..],
+            _ => false,
+        };
+        if is_lp_supply_fresh && is_pool_state_unchanged {
+            self.lp_supply_generation = Some(self.generation);
+        }
+        self.cache_reserves_sol_values();
+        if let (Ok(()), Some(OnUpdateCallback(cb)), Some(reserves_before)) =
+            (&res, self.on_update.as_ref(), reserves_before)
+        {
+            let changed_reserves = self
+                .lst_reserves()
+                .into_iter()
+                .filter(|reserves| !reserves_before.contains(reserves))
+                .map(|(mint, _balance)| mint)
+                .collect();
+            cb(&UpdateSummary {
+                generation: self.generation,
+                changed_reserves,
+            });
+        }
+        res
+    }
+
+    /// [`Self::update_full`] with the accounts in `account_map` fetched at `slot`.
+    ///
+    /// Records `slot` as the `last_updated_slot` of every LST whose reserves account is in
+    /// `account_map`, for [`Self::staleness`].
+    pub fn update_full_at_slot(
+        &mut self,
+        account_map: &HashMap<Pubkey, D>,
+        slot: u64,
+    ) -> anyhow::Result<()> {
+        let res = self.update_full(account_map);
+        // returns the first encountered error, same as update_full()
+        let (fetched, res): (Vec<bool>, _) = {
+            let lst_state_list_acc_data = self.lst_state_list_account.data();
+            match try_lst_state_list(&lst_state_list_acc_data) {
+                Ok(lst_state_list) => (
+                    lst_state_list
+                        .iter()
+                        .zip(self.lst_data_list.iter())
+                        .map(|(lst_state, lst_data)| {
+                            lst_data.as_ref().map_or(false, |ld| {
+                                self.pool_reserves_account(lst_state, ld)
+                                    .map_or(false, |reserves| account_map.contains_key(&reserves))
+                            })
+                        })
+                        .collect(),
+                    res,
+                ),
+                Err(e) => (vec![], res.and(Err(e.into()))),
+            }
+        };
+        self.lst_data_list.iter_mut().zip(fetched).for_each(
+            |(lst_data, is_fetched)| match lst_data {
+                Some(ld) if is_fetched => ld.last_updated_slot = Some(slot),
+                _ => (),
+            },
+        );
+        res
     }
 }
 
@@ -71,6 +258,27 @@ impl<S, L> SPool<S, L> {
             .map_or_else(Vec::new, |pp| pp.get_accounts_to_update_for_liquidity())
     }
 
+    /// Computes and stores [`LstData::reserves_sol_value`] from the current reserves balance
+    /// and sol value calculator for each LST whose cached value is missing or for a different
+    /// reserves balance. Called at the end of `update_full()`.
+    pub fn cache_reserves_sol_values(&mut self) {
+        self.lst_data_list.iter_mut().flatten().for_each(|ld| {
+            let is_fresh = matches!(
+                (ld.reserves_sol_value, ld.reserves_balance),
+                (Some((cached_balance, _)), Some(reserves_balance)) if cached_balance == reserves_balance
+            );
+            if is_fresh {
+                return;
+            }
+            ld.reserves_sol_value = ld.reserves_balance.and_then(|reserves_balance| {
+                ld.sol_val_calc
+                    .lst_to_sol(reserves_balance)
+                    .ok()
+                    .map(|sol_value| (reserves_balance, sol_value.get_min()))
+            });
+        });
+    }
+
     pub fn update_pricing_prog<D: ReadonlyAccountData>(
         &mut self,
         account_map: &HashMap<Pubkey, D>,
@@ -80,6 +288,20 @@ impl<S, L> SPool<S, L> {
         }
         Ok(())
     }
+
+    /// Applies a single pricing program account with [`MutablePricingProg::update_account`].
+    ///
+    /// Returns false if the pricing program is not initialized or does not read `key`.
+    pub fn update_pricing_prog_account<D: ReadonlyAccountData>(
+        &mut self,
+        key: Pubkey,
+        account: D,
+    ) -> anyhow::Result<bool> {
+        match self.pricing_prog.as_mut() {
+            Some(pp) => pp.update_account(key, account),
+            None => Ok(false),
+        }
+    }
 }
 
 impl<S, L: ReadonlyAccountData> SPool<S, L> {
@@ -99,6 +321,85 @@ impl<S, L: ReadonlyAccountData> SPool<S, L> {
         res
     }
 
+    /// Returns (LST mint, `current_slot` - `last_updated_slot`) for every supported LST
+    /// whose reserves were fetched by [`Self::update_full_at_slot`], in lst_state_list order.
+    pub fn staleness(&self, current_slot: u64) -> Vec<(Pubkey, u64)> {
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = match try_lst_state_list(&lst_state_list_data) {
+            Ok(l) => l,
+            Err(_) => return vec![],
+        };
+        lst_state_list
+            .iter()
+            .zip(self.lst_data_list.iter())
+            .filter_map(|(LstState { mint, .. }, lst_data)| {
+                let slot = lst_data.as_ref()?.last_updated_slot?;
+                Some((*mint, current_slot.saturating_sub(slot)))
+            })
+            .collect()
+    }
+
+    /// Returns the accounts required to sync a single LST's sol value:
+    /// lst_state_list, pool_state, its sol value calculator's accounts and its reserves
+    pub fn sync_accounts_for(&self, lst_mint: Pubkey) -> anyhow::Result<Vec<Pubkey>> {
+        let (lst_state, lst_data) = self.find_ready_lst(lst_mint)?;
+        let mut res = Vec::from(self.get_accounts_to_update_base());
+        res.extend(lst_data.sol_val_calc.get_accounts_to_update());
+        res.push(self.pool_reserves_account(&lst_state, lst_data)?);
+        Ok(res)
+    }
+
+    /// Whether enough accounts have been fetched to quote: pool state and pricing program
+    /// are initialized and have their state, and every supported LST has its reserves balance
+    /// and sol value calculator state
+    pub fn is_ready_to_quote(&self) -> bool {
+        self.missing_accounts().is_empty()
+    }
+
+    /// Returns the accounts that must still be fetched and passed to `update()`
+    /// for [`Self::is_ready_to_quote`] to become true.
+    ///
+    /// The pricing program is initialized from the pool state,
+    /// so pool state is also returned if the pricing program is not initialized.
+    /// Its program state and fee accounts are only known once it is initialized.
+    pub fn missing_accounts(&self) -> Vec<Pubkey> {
+        let mut res = Vec::new();
+        if self.pool_state_account.is_none() || self.pricing_prog.is_none() {
+            res.push(self.pool_state_addr);
+        }
+        if let Some(pp) = self.pricing_prog.as_ref() {
+            res.extend(pp.missing_accounts());
+        }
+        let lst_state_list_data = self.lst_state_list_account.data();
+        let lst_state_list = match try_lst_state_list(&lst_state_list_data) {
+            Ok(l) => l,
+            Err(_) => {
+                res.push(self.lst_state_list_addr);
+                return res;
+            }
+        };
+        res.extend(
+            lst_state_list
+                .iter()
+                .zip(self.lst_data_list.iter())
+                .filter_map(|(lst_state, lst_data)| lst_data.as_ref().map(|ld| (lst_state, ld)))
+                .flat_map(|(lst_state, ld)| {
+                    let reserves = ld
+                        .reserves_balance
+                        .is_none()
+                        .then(|| self.pool_reserves_account(lst_state, ld).ok())
+                        .flatten();
+                    reserves
+                        .into_iter()
+                        .chain(ld.sol_val_calc.missing_accounts())
+                }),
+        );
+        // calculators share accounts like the clock sysvar
+        let mut seen = HashSet::with_capacity(res.len());
+        res.retain(|pk| seen.insert(*pk));
+        res
+    }
+
     pub fn get_accounts_to_update_lsts_all(&self) -> Vec<Pubkey> {
         let lst_state_list_data = self.lst_state_list_account.data();
         let lst_state_list = match try_lst_state_list(&lst_state_list_data) {
@@ -136,12 +437,16 @@ impl<S, L: ReadonlyAccountData> SPool<S, L> {
             .collect()
     }
 
-    pub fn update_lst_data_list<D: ReadonlyAccountData>(
+    /// Also corrects an LST's `token_program` if its fetched reserves account
+    /// is owned by a different token program.
+    /// Errors if the reserves account is owned by neither spl-token nor token-2022.
+    pub fn update_lst_data_list<D: ReadonlyAccountData + ReadonlyAccountOwner>(
         &mut self,
         account_map: &HashMap<Pubkey, D>,
     ) -> anyhow::Result<()> {
         // use raw indices to avoid lifetime errs from borrowing immut field (self.lst_state_list)
         // while borrowing mut field (self.lst_data_list)
+        let frozen_clock = self.frozen_clock.clone();
         #[allow(clippy::manual_try_fold)] // we dont want to short-circuit, so dont try_fold()
         (0..self.lst_data_list.len())
             .map(|i| {
@@ -156,13 +461,62 @@ impl<S, L: ReadonlyAccountData> SPool<S, L> {
                     Some(l) => l,
                     None => return Ok(()),
                 };
-                let r = ld.sol_val_calc.update(account_map);
+                // only update calculators that have accounts in account_map
+                // to keep updates with few changed accounts cheap
+                let r = if ld
+                    .sol_val_calc
+                    .get_accounts_to_update()
+                    .iter()
+                    .any(|pk| account_map.contains_key(pk))
+                {
+                    // calculator state may change, cache is refreshed by update_full()
+                    ld.reserves_sol_value = None;
+                    let r = ld.sol_val_calc.update(account_map);
+                    // dont let the fetched clock sysvar overwrite the frozen clock
+                    if let Some(clock) = frozen_clock.as_ref() {
+                        ld.sol_val_calc.set_clock(clock.clone());
+                    }
+                    r
+                } else {
+                    Ok(())
+                };
                 r.and(ata_res.map_or_else(
                     |e| Err(e.into()),
                     |ata| {
-                        if let Some(fetched) = account_map.get(&ata) {
-                            ld.reserves_balance = Some(token_account_balance(fetched)?);
+                        let fetched = match account_map.get(&ata) {
+                            Some(f) => f,
+                            None => return Ok(()),
+                        };
+                        let balance = token_account_balance(fetched)?;
+                        let owner = *fetched.owner();
+                        if owner == ld.token_program {
+                            ld.reserves_balance = Some(balance);
+                            return Ok(());
                         }
+                        let LstState {
+                            mint,
+                            pool_reserves_bump,
+                            ..
+                        } = lst_state_list[i];
+                        if owner != spl_token::ID && owner != spl_token_2022::ID {
+                            return Err(anyhow!(
+                                "Reserves {ata} of LST {mint} owned by {owner}, not a token program"
+                            ));
+                        }
+                        // token_program from lst_list is stale, e.g. the LST migrated token programs
+                        ld.token_program = owner;
+                        let corrected = match self.reserves_account_overrides.get(&mint) {
+                            Some(overridden) => *overridden,
+                            None => Self::reserves_ata(
+                                self.pool_state_addr,
+                                mint,
+                                owner,
+                                pool_reserves_bump,
+                            )?,
+                        };
+                        // fetched account is not the reserves if the ATA changed with the token program,
+                        // the corrected ATA is fetched on the next update
+                        ld.reserves_balance = (corrected == ata).then_some(balance);
                         Ok(())
                     },
                 ))
@@ -176,15 +530,18 @@ impl<S, L: ReadonlyAccountData + Clone> SPool<S, L> {
         account_map: &HashMap<Pubkey, L>,
     ) -> anyhow::Result<()> {
         let new_lst_state_list_account = match account_map.get(&self.lst_state_list_addr) {
-            Some(acc) => acc.clone(),
+            Some(acc) => acc,
             None => return Ok(()),
         };
+        // avoid cloning the whole list every update if nothing changed
+        if new_lst_state_list_account.data()[..] == self.lst_state_list_account.data()[..] {
+            return Ok(());
+        }
         // simple model for diffs:
         // - if new and old list differs in mints, then try to find the mismatches and replace them
-        // - if sol val calc program changed, then just invalidate to None. Otherwise we would need a
-        //   SanctumLstList to reinitialize the KnownLstSolValCalc
-        // - if list was extended, the new entries will just be None and we cant handle it. Otherwise we would need a
-        //   SanctumLstList to initialize the KnownLstSolValCalc
+        // - if sol val calc program changed, or the LST is new (mint changed with no match or list extended),
+        //   initialize it from self.lst_list. This leaves it as None if the sol val calc program is unknown.
+        //   Newly initialized LSTs need another update() to fetch their accounts.
         let lst_state_list_acc_data = self.lst_state_list_account.data();
         let lst_state_list = try_lst_state_list(&lst_state_list_acc_data)?;
         let new_lst_state_list_account_data = new_lst_state_list_account.data();
@@ -199,12 +556,17 @@ impl<S, L: ReadonlyAccountData + Clone> SPool<S, L> {
         {
             drop(lst_state_list_acc_data);
             drop(new_lst_state_list_account_data);
-            self.lst_state_list_account = new_lst_state_list_account;
+            self.lst_state_list_account = Arc::new(new_lst_state_list_account.clone());
             return Ok(());
         }
-        // Either at least 1 sol value calculator changed or mint changed:
+        // Either at least 1 sol value calculator changed, mint changed or list changed in length:
         // rebuild entire lst_data vec by cloning from old vec
-        let mut new_lst_data_list = vec![None; new_lst_state_list.len()];
+        let mut new_lst_data_list: Vec<Option<LstData>> = new_lst_state_list
+            .iter()
+            .map(|new_lst_state| {
+                try_lst_data(&self.lst_list, &self.unlisted_spl_lsts, new_lst_state)
+            })
+            .collect();
         lst_state_list
             .iter()
             .zip(self.lst_data_list.iter())
@@ -242,13 +604,21 @@ impl<S, L: ReadonlyAccountData + Clone> SPool<S, L> {
                             )
                             .cloned()
                     };
-                    *new_lst_data = replacement;
+                    if replacement.is_some() {
+                        *new_lst_data = replacement;
+                    }
                 },
             );
+        if let Some(clock) = self.frozen_clock.as_ref() {
+            new_lst_data_list
+                .iter_mut()
+                .flatten()
+                .for_each(|ld| ld.sol_val_calc.set_clock(clock.clone()));
+        }
         self.lst_data_list = new_lst_data_list;
         drop(lst_state_list_acc_data);
         drop(new_lst_state_list_account_data);
-        self.lst_state_list_account = new_lst_state_list_account;
+        self.lst_state_list_account = Arc::new(new_lst_state_list_account.clone());
         Ok(())
     }
 }
@@ -271,16 +641,52 @@ impl<S: ReadonlyAccountData, L> SPool<S, L> {
             mint_supply(lp_token_mint_acc)?
         };
         self.lp_mint_supply = Some(supply);
+        self.lp_supply_generation = Some(self.generation);
         Ok(())
     }
 }
 
+impl<S, L> SPool<S, L> {
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The latest [`Self::generation`] at which `lp_mint_supply` was known to be current.
+    ///
+    /// Updates that refresh neither pool_state nor the LP mint, or that leave pool_state unchanged,
+    /// carry a fresh supply over to the new generation.
+    pub fn lp_supply_generation(&self) -> Option<u64> {
+        self.lp_supply_generation
+    }
+
+    /// Returns `lp_mint_supply`, erroring if pool_state changed since it was last refreshed
+    pub fn fresh_lp_mint_supply(&self) -> Result<u64, SPoolError> {
+        let supply = self.lp_mint_supply.ok_or(SPoolError::LpMintNotFetched)?;
+        match self.lp_supply_generation {
+            Some(g) if g == self.generation => Ok(supply),
+            _ => Err(SPoolError::LpMintSupplyStale {
+                lp_supply_generation: self.lp_supply_generation,
+                generation: self.generation,
+            }),
+        }
+    }
+}
+
 impl<S: ReadonlyAccountData + Clone, L: ReadonlyAccountData> SPool<S, L> {
     pub fn update_pool_state(&mut self, account_map: &HashMap<Pubkey, S>) -> anyhow::Result<()> {
         let new_pool_state_acc = match account_map.get(&self.pool_state_addr) {
             Some(a) => a,
             None => return Ok(()),
         };
+        // nothing to reinitialize, avoid cloning the account if nothing changed
+        let is_unchanged = self.pricing_prog.is_some()
+            && self
+                .pool_state_account
+                .as_ref()
+                .is_some_and(|old| old.data()[..] == new_pool_state_acc.data()[..]);
+        if is_unchanged {
+            return Ok(());
+        }
         let old_pool_state = self
             .pool_state_data()
             .map_or_else(Err, |d| Ok(*try_pool_state(&d)?));
@@ -306,8 +712,11 @@ impl<S: ReadonlyAccountData + Clone, L: ReadonlyAccountData> SPool<S, L> {
                         })
                         .ok();
                     self.pricing_prog = new_pricing_prog;
+                    if let Some(lazy) = self.lazy_pricing_prog.as_mut() {
+                        lazy.reset();
+                    }
                 }
-                self.pool_state_account = Some(new_pool_state_acc.clone());
+                self.pool_state_account = Some(Arc::new(new_pool_state_acc.clone()));
                 r
             },
         )
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use s_controller_interface::{LstState, PoolState};
 use s_pricing_prog_aggregate::{KnownPricingProg, MutablePricingProg};
 use s_sol_val_calc_prog_aggregate::{
@@ -6,8 +8,9 @@ use s_sol_val_calc_prog_aggregate::{
     SplLstSolValCalcInitKeys, WsolLstSolValCalc,
 };
 use sanctum_lst_list::{PoolInfo, SanctumLst, SplPoolAccounts};
+use solana_program::pubkey::Pubkey;
 
-use crate::LstData;
+use crate::{LstData, UnlistedSplLst};
 
 pub fn try_pricing_prog(
     pool_state: &PoolState,
@@ -19,19 +22,57 @@ pub fn try_pricing_prog(
     )?)
 }
 
+/// Falls back to `unlisted_spl_lsts` for LSTs not on `lst_list`
 pub fn try_lst_data(
     lst_list: &[SanctumLst],
+    unlisted_spl_lsts: &HashMap<Pubkey, UnlistedSplLst>,
+    lst_state: &LstState,
+) -> Option<LstData> {
+    match lst_list.iter().find(|s| s.mint == lst_state.mint) {
+        Some(sanctum_lst) => try_listed_lst_data(sanctum_lst, lst_state),
+        None => try_unlisted_spl_lst_data(unlisted_spl_lsts.get(&lst_state.mint)?, lst_state),
+    }
+}
+
+fn try_unlisted_spl_lst_data(
+    UnlistedSplLst {
+        stake_pool_addr,
+        token_program,
+    }: &UnlistedSplLst,
     LstState {
         mint,
         sol_value_calculator,
         ..
     }: &LstState,
 ) -> Option<LstData> {
-    let SanctumLst {
+    let calc = KnownLstSolValCalc::try_from_spl_calc_program(
+        *sol_value_calculator,
+        SplLstSolValCalcInitKeys {
+            lst_mint: *mint,
+            stake_pool_addr: *stake_pool_addr,
+        },
+    )?;
+    Some(LstData {
+        sol_val_calc: calc,
+        reserves_balance: None,
+        token_program: *token_program,
+        reserves_sol_value: None,
+        last_updated_slot: None,
+    })
+}
+
+fn try_listed_lst_data(
+    SanctumLst {
         pool,
         token_program,
         ..
-    } = lst_list.iter().find(|s| s.mint == *mint)?;
+    }: &SanctumLst,
+    LstState {
+        mint,
+        sol_value_calculator,
+        ..
+    }: &LstState,
+) -> Option<LstData> {
     let calc = match pool {
         PoolInfo::Lido => KnownLstSolValCalc::Lido(LidoLstSolValCalc::default()),
         PoolInfo::Marinade => KnownLstSolValCalc::Marinade(MarinadeLstSolValCalc::default()),
@@ -56,6 +97,8 @@ pub fn try_lst_data(
                 },
             ))
         }
+        // unsupported: there is no sol value calculator program for LP tokens of other S pools
+        // for the pool program to CPI into, so these LSTs cannot be added to a pool
         PoolInfo::SPool(_) => None?,
     };
     if *sol_value_calculator != calc.sol_value_calculator_program_id() {
@@ -65,6 +108,8 @@ pub fn try_lst_data(
             sol_val_calc: calc,
             reserves_balance: None,
             token_program: *token_program,
+            reserves_sol_value: None,
+            last_updated_slot: None,
         })
     }
 }
@@ -0,0 +1,121 @@
+//! Sanity checks for detecting misconfigured pools
+
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use s_controller_lib::{find_lst_state_list_address, find_pool_state_address, try_lst_state_list};
+use s_sol_val_calc_prog_aggregate::{LstSolValCalc, MutableLstSolValCalc};
+use solana_program::sysvar;
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::SPool;
+
+impl<S, L> SPool<S, L> {
+    /// Returns (mint_a, mint_b) pairs of supported LSTs whose sol value calculators
+    /// read from at least one common account, e.g. 2 LSTs pointing to the same stake pool.
+    ///
+    /// Sysvars (clock etc) are shared by design and are not considered collisions.
+    pub fn detect_calc_account_collisions(&self) -> Vec<(Pubkey, Pubkey)> {
+        let calc_accounts: Vec<(Pubkey, HashSet<Pubkey>)> = self
+            .lst_data_list
+            .iter()
+            .flatten()
+            .map(|ld| {
+                (
+                    ld.sol_val_calc.lst_mint(),
+                    ld.sol_val_calc
+                        .get_accounts_to_update()
+                        .into_iter()
+                        .filter(|pk| !sysvar::is_sysvar_id(pk))
+                        .collect(),
+                )
+            })
+            .collect();
+        calc_accounts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (mint_a, accs_a))| {
+                calc_accounts[i + 1..]
+                    .iter()
+                    .filter(move |(_mint_b, accs_b)| !accs_a.is_disjoint(accs_b))
+                    .map(move |(mint_b, _accs_b)| (*mint_a, *mint_b))
+            })
+            .collect()
+    }
+
+    /// Checks that `pool_state_addr` and `lst_state_list_addr`
+    /// are both PDAs of `program_id`
+    pub fn validate_addresses(&self) -> anyhow::Result<()> {
+        let (expected_pool_state, _bump) = find_pool_state_address(self.program_id);
+        if self.pool_state_addr != expected_pool_state {
+            return Err(anyhow!(
+                "pool_state {} is not the pool state of program {}, expected {}",
+                self.pool_state_addr,
+                self.program_id,
+                expected_pool_state
+            ));
+        }
+        let (expected_lst_state_list, _bump) = find_lst_state_list_address(self.program_id);
+        if self.lst_state_list_addr != expected_lst_state_list {
+            return Err(anyhow!(
+                "lst_state_list {} is not the lst state list of program {}, expected {}",
+                self.lst_state_list_addr,
+                self.program_id,
+                expected_lst_state_list
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the mint authority of the pool's LP token mint,
+    /// which is the pool state PDA of `program_id`.
+    ///
+    /// Errors if `pool_state_addr` is not that PDA.
+    pub fn lp_mint_authority(&self) -> anyhow::Result<Pubkey> {
+        let (pool_state, _bump) = find_pool_state_address(self.program_id);
+        if self.pool_state_addr != pool_state {
+            return Err(anyhow!(
+                "pool_state {} is not the pool state of program {}, expected {}",
+                self.pool_state_addr,
+                self.program_id,
+                pool_state
+            ));
+        }
+        Ok(pool_state)
+    }
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Checks that the pool's LP token mint is not also on its LST list,
+    /// which would otherwise result in a swap route from the LP token to itself
+    pub fn validate_lp_mint_not_listed(&self) -> anyhow::Result<()> {
+        let lp_token_mint = self.lp_token_mint()?;
+        let lst_state_list_acc_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_acc_data)?;
+        if lst_state_list.iter().any(|ls| ls.mint == lp_token_mint) {
+            return Err(anyhow!(
+                "LP token mint {lp_token_mint} is also on the LST list"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the pricing program was created for exactly the LSTs on the LST list.
+    /// A pricing program created before LSTs were added to or removed from the list
+    /// may misprice pairs involving them, so call this before a batch of quotes.
+    pub fn assert_pricing_mints_current(&self) -> anyhow::Result<()> {
+        let pricing_mints = self.pricing_prog()?.lst_mints();
+        let lst_state_list_acc_data = self.lst_state_list_account.data();
+        let lst_state_list = try_lst_state_list(&lst_state_list_acc_data)?;
+        let list_mints: HashSet<Pubkey> = lst_state_list.iter().map(|ls| ls.mint).collect();
+        if pricing_mints != list_mints {
+            let missing: Vec<_> = list_mints.difference(&pricing_mints).collect();
+            let extra: Vec<_> = pricing_mints.difference(&list_mints).collect();
+            return Err(anyhow!(
+                "pricing program LST mints not current, missing {missing:?}, extra {extra:?}"
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::{ops::Deref, sync::Arc};
+
+use jupiter_amm_interface::{Quote, QuoteParams};
+use solana_readonly_account::ReadonlyAccountData;
+
+use crate::{SPool, SPoolError};
+
+/// Immutable snapshot of a [`SPool`] that is cheap to clone,
+/// e.g. for sharing across quoting threads.
+///
+/// All of `SPool`'s `&self` methods are available via `Deref`.
+#[derive(Debug)]
+pub struct SPoolQuoteView<S, L>(Arc<SPool<S, L>>);
+
+impl<S, L> Clone for SPoolQuoteView<S, L> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S, L> Deref for SPoolQuoteView<S, L> {
+    type Target = SPool<S, L>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPoolQuoteView<S, L> {
+    pub fn quote(&self, quote_params: &QuoteParams) -> anyhow::Result<Quote> {
+        self.0
+            .quote_full(quote_params)
+            .map_err(SPoolError::into_anyhow)
+    }
+}
+
+impl<S: Clone, L: Clone> SPool<S, L> {
+    /// Snapshots the current state of `self`.
+    /// Subsequent updates to `self` are not reflected in the returned view.
+    pub fn to_view(&self) -> SPoolQuoteView<S, L> {
+        SPoolQuoteView(Arc::new(self.clone()))
+    }
+}
@@ -0,0 +1,116 @@
+use jupiter_amm_interface::{SwapMode, SwapParams};
+use solana_readonly_account::ReadonlyAccountData;
+use solana_sdk::{instruction::Instruction, system_instruction};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use spl_token::{
+    instruction::{close_account, sync_native},
+    native_mint,
+};
+
+use crate::SPool;
+
+/// A swap instruction along with the instructions that wrap native SOL into
+/// and unwrap it out of WSOL around it, see [`SPool::swap_ixs_with_wsol_wrap`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WsolWrappedSwapIxs {
+    /// To be executed before `swap`
+    pub prepend: Vec<Instruction>,
+    pub swap: Instruction,
+    /// To be executed after `swap`
+    pub append: Vec<Instruction>,
+}
+
+impl WsolWrappedSwapIxs {
+    /// All instructions in execution order
+    pub fn into_ixs(self) -> Vec<Instruction> {
+        let Self {
+            prepend,
+            swap,
+            append,
+        } = self;
+        prepend
+            .into_iter()
+            .chain(std::iter::once(swap))
+            .chain(append)
+            .collect()
+    }
+}
+
+impl<S: ReadonlyAccountData, L: ReadonlyAccountData> SPool<S, L> {
+    /// Same as [`Self::swap_ix`], but swaps native SOL in or out through the pool's WSOL LST.
+    ///
+    /// The WSOL side's token account in `swap_params` is replaced with the WSOL ATA of
+    /// `token_transfer_authority`, who also pays for creating it. If the source is WSOL,
+    /// `in_amount` lamports are transferred into the ATA and synced before the swap.
+    ///
+    /// If `wsol_ata_exists` is false, the ATA is created before the swap and closed after it,
+    /// returning any WSOL left in it, including WSOL output, as native SOL.
+    /// Otherwise the ATA is left open and WSOL output stays wrapped.
+    ///
+    /// No wrapping instructions are added if neither side of the swap is WSOL.
+    pub fn swap_ixs_with_wsol_wrap(
+        &self,
+        swap_params: &SwapParams,
+        swap_mode: SwapMode,
+        wsol_ata_exists: bool,
+    ) -> anyhow::Result<WsolWrappedSwapIxs> {
+        let is_src_wsol = swap_params.source_mint == native_mint::ID;
+        let is_dst_wsol = swap_params.destination_mint == native_mint::ID;
+        if !is_src_wsol && !is_dst_wsol {
+            return Ok(WsolWrappedSwapIxs {
+                prepend: vec![],
+                swap: self.swap_ix(swap_params, swap_mode)?,
+                append: vec![],
+            });
+        }
+
+        let owner = swap_params.token_transfer_authority;
+        let wsol_ata = get_associated_token_address(&owner, &native_mint::ID);
+        let swap_params = SwapParams {
+            source_token_account: if is_src_wsol {
+                wsol_ata
+            } else {
+                swap_params.source_token_account
+            },
+            destination_token_account: if is_dst_wsol {
+                wsol_ata
+            } else {
+                swap_params.destination_token_account
+            },
+            ..*swap_params
+        };
+
+        let mut prepend = vec![];
+        let mut append = vec![];
+        if !wsol_ata_exists {
+            prepend.push(create_associated_token_account_idempotent(
+                &owner,
+                &owner,
+                &native_mint::ID,
+                &spl_token::ID,
+            ));
+            append.push(close_account(
+                &spl_token::ID,
+                &wsol_ata,
+                &owner,
+                &owner,
+                &[],
+            )?);
+        }
+        if is_src_wsol {
+            prepend.push(system_instruction::transfer(
+                &owner,
+                &wsol_ata,
+                swap_params.in_amount,
+            ));
+            prepend.push(sync_native(&spl_token::ID, &wsol_ata)?);
+        }
+        Ok(WsolWrappedSwapIxs {
+            prepend,
+            swap: self.swap_ix(&swap_params, swap_mode)?,
+            append,
+        })
+    }
+}
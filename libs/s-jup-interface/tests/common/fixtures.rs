@@ -0,0 +1,69 @@
+use flat_fee_test_utils::MockFeeAccountArgs;
+use marinade_keys::msol;
+use s_controller_test_utils::{
+    jito_marinade_flat_fee_program_test, JitoMarinadeProgramTestArgs, MockProtocolFeeBps,
+};
+use s_jup_interface::SPoolJup;
+use solana_program_test::{BanksClient, ProgramTest, ProgramTestContext};
+use solana_sdk::{clock::Clock, pubkey::Pubkey};
+use test_utils::{jitosol, JITO_STAKE_POOL_LAST_UPDATE_EPOCH};
+
+use crate::{fully_init_amm, MiscProgramTest};
+
+pub const JITO_MARINADE_BASIC_LP_TOKEN_SUPPLY: u64 = 20_000_000_000;
+
+/// 10 SOL worth of each LST, 4 bps flat fees for all LSTs,
+/// 10% trading and lp protocol fees
+pub fn jito_marinade_basic_program_test(lp_token_mint: Pubkey) -> ProgramTest {
+    jito_marinade_flat_fee_program_test(
+        JitoMarinadeProgramTestArgs {
+            jitosol_sol_value: 10_000_000_000,
+            msol_sol_value: 10_000_000_000,
+            jitosol_reserves: 10_000_000_000,
+            msol_reserves: 10_000_000_000,
+            jitosol_protocol_fee_accumulator: 0,
+            msol_protocol_fee_accumulator: 0,
+            lp_token_mint,
+            lp_token_supply: JITO_MARINADE_BASIC_LP_TOKEN_SUPPLY,
+        },
+        flat_fee_interface::ProgramState {
+            manager: Pubkey::default(),
+            lp_withdrawal_fee_bps: 0,
+        },
+        [
+            MockFeeAccountArgs {
+                input_fee_bps: 4,
+                output_fee_bps: 4,
+                lst_mint: jitosol::ID,
+            },
+            MockFeeAccountArgs {
+                input_fee_bps: 4,
+                output_fee_bps: 4,
+                lst_mint: msol::ID,
+            },
+        ],
+        MockProtocolFeeBps {
+            trading: 1000,
+            lp: 1000,
+        },
+    )
+    .add_s_program()
+}
+
+/// Starts `pt` with the clock set to the jito stake pool's last updated epoch
+pub async fn start_jito_marinade(pt: ProgramTest) -> BanksClient {
+    let ctx = pt.start_with_context().await;
+    ctx.set_sysvar(&Clock {
+        epoch: JITO_STAKE_POOL_LAST_UPDATE_EPOCH,
+        ..Default::default()
+    });
+    let ProgramTestContext { banks_client, .. } = ctx;
+    banks_client
+}
+
+/// Returns a fully initialized [`SPoolJup`] for [`jito_marinade_basic_program_test`]
+pub async fn jito_marinade_basic_amm() -> (BanksClient, SPoolJup) {
+    let mut bc = start_jito_marinade(jito_marinade_basic_program_test(Pubkey::new_unique())).await;
+    let s = fully_init_amm(&mut bc, s_controller_lib::program::ID).await;
+    (bc, s)
+}
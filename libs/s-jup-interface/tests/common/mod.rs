@@ -1,7 +1,9 @@
+mod fixtures;
 mod misc;
 mod quote_swap;
 mod update;
 
+pub use fixtures::*;
 pub use misc::*;
 pub use quote_swap::*;
 pub use update::*;
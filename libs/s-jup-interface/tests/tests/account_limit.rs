@@ -0,0 +1,51 @@
+use jupiter_amm_interface::SwapParams;
+use marinade_keys::msol;
+use s_jup_interface::{check_tx_account_limit, SPoolError, MAX_TX_ACCOUNTS};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn account_limit_guard_fires() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let swap_params = SwapParams {
+        in_amount: 1_000_000_000,
+        out_amount: 0,
+        source_mint: jitosol::ID,
+        destination_mint: msol::ID,
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        token_transfer_authority: Pubkey::new_unique(),
+        open_order_address: None,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &Pubkey::default(),
+        missing_dynamic_accounts_as_default: false,
+    };
+    let metas = s
+        .get_swap_and_account_metas_within_limit(&swap_params, 0)
+        .unwrap()
+        .account_metas;
+    assert!(matches!(
+        s.get_swap_and_account_metas_within_limit(&swap_params, MAX_TX_ACCOUNTS),
+        Err(SPoolError::TooManyAccounts {
+            max: MAX_TX_ACCOUNTS,
+            ..
+        })
+    ));
+
+    // simulate sol value calculators with many accounts
+    let mut large = metas.clone();
+    large.extend(
+        (0..MAX_TX_ACCOUNTS).map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false)),
+    );
+    assert!(matches!(
+        check_tx_account_limit(&large, 0),
+        Err(SPoolError::TooManyAccounts { accounts, .. }) if accounts > MAX_TX_ACCOUNTS
+    ));
+
+    // duplicate metas are only counted once
+    let mut duplicated = metas.clone();
+    duplicated.extend(metas.iter().cloned());
+    check_tx_account_limit(&duplicated, 0).unwrap();
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use marinade_keys::{marinade_state, msol};
+use s_controller_interface::LstState;
+use s_controller_lib::{find_pool_reserves_address, try_lst_state_list_mut, FindLstPdaAtaKeys};
+use s_jup_interface::{LstData, SPoolJup};
+use s_sol_val_calc_prog_aggregate::{
+    KnownLstSolValCalc, SplLstSolValCalc, SplLstSolValCalcInitKeys,
+};
+use solana_sdk::{account::Account, pubkey::Pubkey, sysvar};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn sync_accounts_for_msol() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let accounts = s.sync_accounts_for(msol::ID).unwrap();
+    let (msol_reserves, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    for expected in [
+        s_controller_lib::program::LST_STATE_LIST_ID,
+        s_controller_lib::program::POOL_STATE_ID,
+        marinade_state::ID,
+        msol_reserves,
+    ] {
+        assert!(accounts.contains(&expected));
+    }
+}
+
+#[tokio::test]
+async fn accounts_to_update_classified() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let (static_accounts, derived) = s.get_accounts_to_update_classified();
+    for expected_static in [
+        s_controller_lib::program::LST_STATE_LIST_ID,
+        s_controller_lib::program::POOL_STATE_ID,
+    ] {
+        assert!(static_accounts.contains(&expected_static));
+        assert!(!derived.contains(&expected_static));
+    }
+    for mint in [jitosol::ID, msol::ID] {
+        let (lst_state, lst_data) = s.find_ready_lst(mint).unwrap();
+        let reserves = s.pool_reserves_account(&lst_state, lst_data).unwrap();
+        assert!(derived.contains(&reserves));
+        assert!(!static_accounts.contains(&reserves));
+    }
+    assert!(derived.contains(&s.lp_token_mint().unwrap()));
+    assert_eq!(
+        static_accounts.len() + derived.len(),
+        s.get_accounts_to_update_full().len()
+    );
+}
+
+#[tokio::test]
+async fn static_reserves_ata_matches_instance() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    for mint in [jitosol::ID, msol::ID] {
+        let (lst_state, lst_data) = s.find_ready_lst(mint).unwrap();
+        let expected = s.pool_reserves_account(&lst_state, lst_data).unwrap();
+        let derived = SPoolJup::reserves_ata(
+            s.pool_state_addr,
+            lst_state.mint,
+            lst_data.token_program,
+            lst_state.pool_reserves_bump,
+        )
+        .unwrap();
+        assert_eq!(derived, expected);
+    }
+}
+
+#[test]
+fn accounts_to_update_deduped() {
+    let [mint_a, mint_b, shared_pool] = [(); 3].map(|_| Pubkey::new_unique());
+    let mut lst_state_list_account = Account {
+        data: vec![0; 2 * std::mem::size_of::<LstState>()],
+        owner: s_controller_lib::program::ID,
+        ..Default::default()
+    };
+    let lst_state_list = try_lst_state_list_mut(&mut lst_state_list_account.data).unwrap();
+    lst_state_list[0].mint = mint_a;
+    lst_state_list[1].mint = mint_b;
+    let s = SPoolJup {
+        lst_state_list_account: lst_state_list_account.into(),
+        lst_data_list: [mint_a, mint_b]
+            .map(|lst_mint| {
+                Some(LstData {
+                    sol_val_calc: KnownLstSolValCalc::Spl(SplLstSolValCalc::from_keys(
+                        SplLstSolValCalcInitKeys {
+                            lst_mint,
+                            stake_pool_addr: shared_pool,
+                        },
+                    )),
+                    reserves_balance: None,
+                    token_program: spl_token::ID,
+                    reserves_sol_value: None,
+                    last_updated_slot: None,
+                })
+            })
+            .into(),
+        ..Default::default()
+    };
+
+    let accounts = s.get_accounts_to_update_full();
+    let unique: HashSet<_> = accounts.iter().collect();
+    assert_eq!(unique.len(), accounts.len());
+    for shared in [sysvar::clock::ID, shared_pool] {
+        assert_eq!(accounts.iter().filter(|pk| **pk == shared).count(), 1);
+    }
+    assert_eq!(
+        accounts[..2],
+        [
+            s_controller_lib::program::LST_STATE_LIST_ID,
+            s_controller_lib::program::POOL_STATE_ID,
+        ]
+    );
+}
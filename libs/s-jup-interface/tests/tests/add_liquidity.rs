@@ -1,16 +1,23 @@
 use flat_fee_test_utils::MockFeeAccountArgs;
 use jupiter_amm_interface::{QuoteParams, SwapMode};
 use marinade_keys::msol;
+use s_controller_interface::AddLiquidityIxData;
+use s_controller_lib::{
+    add_liquidity_ix_by_mint_full, program::POOL_STATE_ID, try_lst_state_list,
+    AddLiquidityByMintFreeArgs, AddLiquidityIxAmts, AddRemoveLiquidityAccountSuffixes,
+};
 use s_controller_test_utils::{
     jito_marinade_flat_fee_program_test, JitoMarinadeProgramTestArgs, MockProtocolFeeBps,
 };
+use s_pricing_prog_aggregate::PricingProg;
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
 use sanctum_solana_test_utils::ExtendedProgramTest;
 use sanctum_token_lib::MintWithTokenProgram;
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer};
 use test_utils::{jitosol, JITO_STAKE_POOL_LAST_UPDATE_EPOCH};
 
-use crate::{assert_quote_swap_eq, fully_init_amm, MiscProgramTest};
+use crate::{assert_quote_swap_eq, fully_init_amm, jito_marinade_basic_amm, MiscProgramTest};
 
 #[tokio::test]
 async fn add_liquidity_jito_basic() {
@@ -92,3 +99,127 @@ async fn add_liquidity_jito_basic() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn add_liquidity_exact_lp_out_fed_forward() {
+    const LP_OUT: u64 = 1_234_567_890;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let lp_token_mint = s.lp_token_mint().unwrap();
+    let deposit = s
+        .quote_add_liquidity_exact_lp_out(jitosol::ID, LP_OUT)
+        .unwrap();
+    let quote_lp_out = |amount| {
+        s.quote_full(&QuoteParams {
+            amount,
+            input_mint: jitosol::ID,
+            output_mint: lp_token_mint,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap()
+        .out_amount
+    };
+    assert!(quote_lp_out(deposit) >= LP_OUT);
+    assert!(quote_lp_out(deposit - 1) < LP_OUT);
+}
+
+#[tokio::test]
+async fn add_liquidity_exact_lp_out_bootstrap() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.lp_mint_supply = Some(0);
+    // first LP gets the pool's existing 20 SOL worth of sol value
+    assert_eq!(
+        s.quote_add_liquidity_exact_lp_out(jitosol::ID, 1_000_000_000)
+            .unwrap(),
+        0
+    );
+    assert!(
+        s.quote_add_liquidity_exact_lp_out(jitosol::ID, 30_000_000_000)
+            .unwrap()
+            > 0
+    );
+}
+
+#[tokio::test]
+async fn quote_add_liquidity_non_empty_pool() {
+    const AMT: u64 = 1_000_000_000;
+
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let lp_token_mint = s.lp_token_mint().unwrap();
+    let quote = s.quote_add_liquidity(jitosol::ID, AMT).unwrap();
+    let via_quote_full = s
+        .quote_full(&QuoteParams {
+            amount: AMT,
+            input_mint: jitosol::ID,
+            output_mint: lp_token_mint,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    assert_eq!(quote.out_amount, via_quote_full.out_amount);
+    assert_eq!(quote.fee_mint, jitosol::ID);
+    assert!(quote.out_amount > 0);
+
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == jitosol::ID)
+        .unwrap()
+        .reserves_balance = None;
+    assert!(s.quote_add_liquidity(jitosol::ID, AMT).is_err());
+}
+
+#[tokio::test]
+async fn add_liquidity_by_mint_resolves_lst_index() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let pool_state = s.pool_state_account.as_deref().unwrap();
+    let lst_state_list = try_lst_state_list(&s.lst_state_list_account.data).unwrap();
+    let lp_token_mint = s.lp_token_mint().unwrap();
+    let free_args = |mint| AddLiquidityByMintFreeArgs {
+        signer: Pubkey::new_unique(),
+        src_lst_acc: Pubkey::new_unique(),
+        dst_lp_acc: Pubkey::new_unique(),
+        pool_state,
+        lst_state_list: &*s.lst_state_list_account,
+        lst_mint: MintWithTokenProgram {
+            pubkey: mint,
+            token_program: spl_token::ID,
+        },
+    };
+    for mint in [jitosol::ID, msol::ID] {
+        let expected_index = lst_state_list
+            .iter()
+            .position(|lst_state| lst_state.mint == mint)
+            .unwrap();
+        let (keys, lst_index, _program_ids) = free_args(mint).resolve().unwrap();
+        assert_eq!(lst_index, expected_index);
+        assert_eq!(keys.lst_mint, mint);
+        assert_eq!(keys.lp_token_mint, lp_token_mint);
+        assert_eq!(keys.pool_state, POOL_STATE_ID);
+
+        let (_, lst_data) = s.find_ready_lst(mint).unwrap();
+        let lst_calculator_accounts = lst_data.sol_val_calc.ix_accounts();
+        let pricing_program_price_lp_accounts = s
+            .pricing_prog()
+            .unwrap()
+            .price_lp_tokens_to_mint_accounts(mint)
+            .unwrap();
+        let ix = add_liquidity_ix_by_mint_full(
+            free_args(mint),
+            AddLiquidityIxAmts {
+                lst_amount: 1_000_000_000,
+                min_lp_out: 0,
+            },
+            AddRemoveLiquidityAccountSuffixes {
+                lst_calculator_accounts: &lst_calculator_accounts,
+                pricing_program_price_lp_accounts: &pricing_program_price_lp_accounts,
+            },
+        )
+        .unwrap();
+        let AddLiquidityIxData(args) = AddLiquidityIxData::deserialize(&ix.data).unwrap();
+        assert_eq!(args.lst_index, expected_index as u32);
+        assert_eq!(
+            usize::from(args.lst_value_calc_accs),
+            lst_calculator_accounts.len()
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::SPoolError;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+const JITOSOL_TO_MSOL: QuoteParams = QuoteParams {
+    amount: 1_000_000_000,
+    input_mint: jitosol::ID,
+    output_mint: msol::ID,
+    swap_mode: SwapMode::ExactIn,
+};
+
+#[tokio::test]
+async fn quotes_for_non_allowlisted_mints_error() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.quote_full(&JITOSOL_TO_MSOL).unwrap();
+
+    s.set_allowlist(Some(&[jitosol::ID]));
+    assert!(s.find_ready_lst(jitosol::ID).is_ok());
+    assert!(matches!(
+        s.quote_full(&JITOSOL_TO_MSOL),
+        Err(SPoolError::LstNotAllowed(mint)) if mint == msol::ID
+    ));
+
+    s.set_allowlist(Some(&[jitosol::ID, msol::ID]));
+    s.quote_full(&JITOSOL_TO_MSOL).unwrap();
+
+    s.set_allowlist(Some(&[]));
+    assert!(matches!(
+        s.find_ready_lst(jitosol::ID),
+        Err(SPoolError::LstNotAllowed(_))
+    ));
+
+    s.set_allowlist(None);
+    s.quote_full(&JITOSOL_TO_MSOL).unwrap();
+}
@@ -0,0 +1,319 @@
+use std::sync::Arc;
+
+use jupiter_amm_interface::{
+    Amm, KeyedAccount, QuoteParams, Swap, SwapAndAccountMetas, SwapMode, SwapParams,
+};
+use marinade_keys::msol;
+use pricing_programs_interface::PriceExactInKeys;
+use s_controller_lib::{
+    swap_exact_in_ix_by_mint_full, try_pool_state, try_pool_state_mut,
+    SrcDstLstSolValueCalcAccountSuffixes, SwapByMintsFreeArgs, SwapExactInAmounts,
+};
+use s_jup_interface::{swap_and_account_metas_to_ix, SPoolInitKeys, SPoolJup};
+use s_pricing_prog_aggregate::PricingProg;
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use sanctum_token_lib::MintWithTokenProgram;
+use solana_sdk::pubkey::Pubkey;
+use test_utils::jitosol;
+
+use crate::{
+    jito_marinade_basic_amm, jito_marinade_basic_program_test, start_jito_marinade,
+    UpdatingSPoolJup,
+};
+
+#[tokio::test]
+async fn amm_quote_matches_inherent_exact_in() {
+    const AMT: u64 = 1_000_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let trait_quote = s
+        .quote(&QuoteParams {
+            amount: AMT,
+            input_mint: jitosol::ID,
+            output_mint: msol::ID,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    let [inherent_quote] = <[_; 1]>::try_from(
+        s.quote_swap_exact_in_curve(jitosol::ID, msol::ID, &[AMT])
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(trait_quote.in_amount, inherent_quote.in_amount);
+    assert_eq!(trait_quote.out_amount, inherent_quote.out_amount);
+    assert_eq!(trait_quote.fee_amount, inherent_quote.fee_amount);
+    assert_eq!(trait_quote.fee_mint, inherent_quote.fee_mint);
+    assert_eq!(trait_quote.fee_pct, inherent_quote.fee_pct);
+    assert_eq!(
+        trait_quote.not_enough_liquidity,
+        inherent_quote.not_enough_liquidity
+    );
+}
+
+#[tokio::test]
+async fn amm_quote_unsupported_pairs_err() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let unknown_mint = Pubkey::new_unique();
+    for (input_mint, output_mint) in [
+        (jitosol::ID, unknown_mint),
+        (unknown_mint, msol::ID),
+        (jitosol::ID, jitosol::ID),
+    ] {
+        for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+            assert!(s
+                .quote(&QuoteParams {
+                    amount: 1_000_000_000,
+                    input_mint,
+                    output_mint,
+                    swap_mode,
+                })
+                .is_err());
+        }
+    }
+}
+
+#[tokio::test]
+async fn supports_exact_out_for_lst_pairs_only() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let lp_token_mint = s.lp_token_mint().unwrap();
+    assert!(s.supports_exact_out());
+    for (input_mint, output_mint) in [(jitosol::ID, msol::ID), (msol::ID, jitosol::ID)] {
+        assert!(s.supports_exact_out_for(input_mint, output_mint));
+        assert!(s
+            .quote(&QuoteParams {
+                amount: 1_000_000_000,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactOut,
+            })
+            .is_ok());
+    }
+    for (input_mint, output_mint) in [(jitosol::ID, lp_token_mint), (lp_token_mint, msol::ID)] {
+        assert!(!s.supports_exact_out_for(input_mint, output_mint));
+        assert!(s
+            .quote(&QuoteParams {
+                amount: 1_000_000_000,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactOut,
+            })
+            .is_err());
+    }
+}
+
+#[tokio::test]
+async fn amm_swap_and_account_metas_matches_ix_by_mint_full() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let signer = Pubkey::new_unique();
+    let src_lst_acc = Pubkey::new_unique();
+    let dst_lst_acc = Pubkey::new_unique();
+    let SwapAndAccountMetas {
+        swap,
+        account_metas,
+    } = s
+        .get_swap_and_account_metas(&SwapParams {
+            in_amount: 1_000_000_000,
+            out_amount: 1_000,
+            source_mint: jitosol::ID,
+            destination_mint: msol::ID,
+            source_token_account: src_lst_acc,
+            destination_token_account: dst_lst_acc,
+            token_transfer_authority: signer,
+            open_order_address: None,
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Pubkey::default(),
+            missing_dynamic_accounts_as_default: false,
+        })
+        .unwrap();
+    assert!(matches!(swap, Swap::SanctumS { .. }));
+
+    let (_, jitosol_data) = s.find_ready_lst(jitosol::ID).unwrap();
+    let (_, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    let pricing_prog = s.pricing_prog().unwrap();
+    let expected_ix = swap_exact_in_ix_by_mint_full(
+        SwapByMintsFreeArgs {
+            signer,
+            src_lst_acc,
+            dst_lst_acc,
+            src_lst_mint: MintWithTokenProgram {
+                pubkey: jitosol::ID,
+                token_program: spl_token::ID,
+            },
+            dst_lst_mint: MintWithTokenProgram {
+                pubkey: msol::ID,
+                token_program: spl_token::ID,
+            },
+            lst_state_list: &*s.lst_state_list_account,
+        },
+        SwapExactInAmounts {
+            min_amount_out: 1_000,
+            amount: 1_000_000_000,
+        },
+        SrcDstLstSolValueCalcAccountSuffixes {
+            src_lst_calculator_accounts: &jitosol_data.sol_val_calc.ix_accounts(),
+            dst_lst_calculator_accounts: &msol_data.sol_val_calc.ix_accounts(),
+        },
+        &pricing_prog
+            .price_exact_in_accounts(PriceExactInKeys {
+                input_lst_mint: jitosol::ID,
+                output_lst_mint: msol::ID,
+            })
+            .unwrap(),
+        pricing_prog.pricing_program_id(),
+    )
+    .unwrap();
+
+    // first account is the S program for the jup program to CPI into
+    assert_eq!(account_metas[0].pubkey, s.program_id);
+    assert_eq!(account_metas[1..], expected_ix.accounts);
+}
+
+#[tokio::test]
+async fn swap_and_account_metas_to_ix_matches_swap_ix() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let lp_token_mint = s.lp_token_mint().unwrap();
+    let jupiter_program_id = Pubkey::default();
+    for (source_mint, destination_mint) in [
+        (jitosol::ID, msol::ID),
+        (jitosol::ID, lp_token_mint),
+        (lp_token_mint, msol::ID),
+    ] {
+        let swap_params = SwapParams {
+            in_amount: 1_000_000_000,
+            out_amount: 1_000,
+            source_mint,
+            destination_mint,
+            source_token_account: Pubkey::new_unique(),
+            destination_token_account: Pubkey::new_unique(),
+            token_transfer_authority: Pubkey::new_unique(),
+            open_order_address: None,
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &jupiter_program_id,
+            missing_dynamic_accounts_as_default: false,
+        };
+        let original = s.swap_ix(&swap_params, SwapMode::ExactIn).unwrap();
+        let reconstructed = swap_and_account_metas_to_ix(
+            s.program_id,
+            s.get_swap_and_account_metas(&swap_params).unwrap(),
+            original.data.clone(),
+        )
+        .unwrap();
+        assert_eq!(reconstructed, original);
+
+        assert!(swap_and_account_metas_to_ix(
+            Pubkey::new_unique(),
+            s.get_swap_and_account_metas(&swap_params).unwrap(),
+            original.data,
+        )
+        .is_err());
+    }
+}
+
+#[tokio::test]
+async fn amm_swap_variants_are_sanctum_s() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let lp_token_mint = s.lp_token_mint().unwrap();
+    let jupiter_program_id = Pubkey::default();
+    let swap_params = |in_amount, source_mint, destination_mint| SwapParams {
+        in_amount,
+        out_amount: 1_000,
+        source_mint,
+        destination_mint,
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        token_transfer_authority: Pubkey::new_unique(),
+        open_order_address: None,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &jupiter_program_id,
+        missing_dynamic_accounts_as_default: false,
+    };
+    let swap_of = |params: &SwapParams| s.get_swap_and_account_metas(params).unwrap().swap;
+
+    // exact in
+    assert!(matches!(
+        swap_of(&swap_params(1_000_000_000, jitosol::ID, msol::ID)),
+        Swap::SanctumS { .. }
+    ));
+    // in_amount == 0 => exact out
+    assert!(matches!(
+        swap_of(&swap_params(0, jitosol::ID, msol::ID)),
+        Swap::SanctumS { .. }
+    ));
+    assert!(matches!(
+        swap_of(&swap_params(1_000_000_000, jitosol::ID, lp_token_mint)),
+        Swap::SanctumSAddLiquidity { .. }
+    ));
+    assert!(matches!(
+        swap_of(&swap_params(1_000_000_000, lp_token_mint, msol::ID)),
+        Swap::SanctumSRemoveLiquidity { .. }
+    ));
+}
+
+#[tokio::test]
+async fn amm_reserve_mints_available_before_update() {
+    let lp_token_mint = Pubkey::new_unique();
+    let mut bc = start_jito_marinade(jito_marinade_basic_program_test(lp_token_mint)).await;
+    let program_id = s_controller_lib::program::ID;
+    let SPoolInitKeys { lst_state_list, .. } = SPoolJup::init_keys(program_id);
+    let s = SPoolJup::from_keyed_account(&KeyedAccount {
+        key: lst_state_list,
+        account: bc.get_account_unwrapped(lst_state_list).await,
+        params: Some(serde_json::Value::String(program_id.to_string())),
+    })
+    .unwrap();
+
+    let mut reserve_mints = s.get_reserve_mints();
+    reserve_mints.sort();
+    let mut expected = vec![jitosol::ID, msol::ID];
+    expected.sort();
+    assert_eq!(reserve_mints, expected);
+    // LP token mint unknown until pool state is fetched
+    assert!(!s.supports_exact_out_for(jitosol::ID, msol::ID));
+
+    let s = s.update_with_banks(&mut bc).await;
+    assert!(s.get_reserve_mints().contains(&lp_token_mint));
+}
+
+#[tokio::test]
+async fn clone_amm_shares_accounts_but_not_quoting_state() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let expected = s.quote(&params).unwrap();
+    assert_eq!(
+        s.clone_amm().quote(&params).unwrap().out_amount,
+        expected.out_amount
+    );
+
+    let mut cloned = s.clone();
+    assert!(Arc::ptr_eq(
+        &s.lst_state_list_account,
+        &cloned.lst_state_list_account
+    ));
+    assert!(Arc::ptr_eq(
+        s.pool_state_account.as_ref().unwrap(),
+        cloned.pool_state_account.as_ref().unwrap()
+    ));
+
+    let lp_mint_supply = s.lp_mint_supply;
+    assert!(lp_mint_supply.is_some_and(|supply| supply > 0));
+    cloned.lp_mint_supply = Some(0);
+    assert_eq!(s.lp_mint_supply, lp_mint_supply);
+
+    // mutating a shared account copies it instead of writing through to the original
+    try_pool_state_mut(&mut Arc::make_mut(cloned.pool_state_account.as_mut().unwrap()).data)
+        .unwrap()
+        .total_sol_value = 0;
+    assert_ne!(
+        try_pool_state(&s.pool_state_data().unwrap())
+            .unwrap()
+            .total_sol_value,
+        0
+    );
+    assert_eq!(s.quote(&params).unwrap().out_amount, expected.out_amount);
+}
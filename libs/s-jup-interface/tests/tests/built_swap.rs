@@ -0,0 +1,284 @@
+use jupiter_amm_interface::{QuoteParams, SwapMode, SwapParams};
+use marinade_keys::msol;
+use s_controller_interface::{
+    SwapExactInIxArgs, SwapExactInIxData, SwapExactOutIxData, SyncSolValueIxArgs,
+    SyncSolValueIxData, SWAP_EXACT_IN_IX_DISCM, SWAP_EXACT_OUT_IX_ACCOUNTS_LEN,
+    SYNC_SOL_VALUE_IX_DISCM,
+};
+use s_controller_lib::{try_find_lst_mint_on_list, try_lst_state_list, try_pool_state};
+use s_jup_interface::{BuiltSwap, SwapAccounts};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn build_swap_exact_in_decodes_args() {
+    const IN_AMOUNT: u64 = 1_000_000_000;
+    const OUT_AMOUNT: u64 = 999_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let wallet = Keypair::new();
+    let built: BuiltSwap = s
+        .build_swap_exact_in(&SwapParams {
+            in_amount: IN_AMOUNT,
+            out_amount: OUT_AMOUNT,
+            source_mint: jitosol::ID,
+            destination_mint: msol::ID,
+            source_token_account: Pubkey::new_unique(),
+            destination_token_account: Pubkey::new_unique(),
+            token_transfer_authority: wallet.pubkey(),
+            open_order_address: None,
+            quote_mint_to_referrer: None,
+            jupiter_program_id: &Pubkey::default(),
+            missing_dynamic_accounts_as_default: false,
+        })
+        .unwrap();
+
+    let lst_state_list = try_lst_state_list(&s.lst_state_list_account.data).unwrap();
+    let index_of = |mint: Pubkey| {
+        lst_state_list
+            .iter()
+            .position(|ls| ls.mint == mint)
+            .unwrap() as u32
+    };
+
+    assert_eq!(built.program_id, s.program_id);
+    assert_eq!(built.discriminant, SWAP_EXACT_IN_IX_DISCM);
+    assert_eq!(built.args.amount, IN_AMOUNT);
+    assert_eq!(built.args.min_amount_out, OUT_AMOUNT);
+    assert_eq!(built.src_lst_index(), index_of(jitosol::ID));
+    assert_eq!(built.dst_lst_index(), index_of(msol::ID));
+    assert!(built.src_lst_value_calc_accs() > 0);
+    assert!(built.dst_lst_value_calc_accs() > 0);
+    assert!(built
+        .account_metas
+        .iter()
+        .any(|meta| meta.pubkey == wallet.pubkey() && meta.is_signer));
+}
+
+#[tokio::test]
+async fn quote_and_build_exact_in_applies_slippage() {
+    const AMT: u64 = 1_000_000_000;
+    const SLIPPAGE_BPS: u16 = 50;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let swap_accounts = SwapAccounts {
+        signer: Pubkey::new_unique(),
+        src_lst_acc: Pubkey::new_unique(),
+        dst_lst_acc: Pubkey::new_unique(),
+    };
+    let quote_params = QuoteParams {
+        amount: AMT,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let (quote, ix) = s
+        .quote_and_build_exact_in(&quote_params, swap_accounts, SLIPPAGE_BPS)
+        .unwrap();
+    assert_eq!(
+        quote.out_amount,
+        s.quote_full(&quote_params).unwrap().out_amount
+    );
+
+    let built = BuiltSwap::try_from(ix).unwrap();
+    assert_eq!(built.args.amount, AMT);
+    assert_eq!(
+        built.args.min_amount_out,
+        quote.out_amount * (10_000 - u64::from(SLIPPAGE_BPS)) / 10_000
+    );
+    assert!(built
+        .account_metas
+        .iter()
+        .any(|meta| meta.pubkey == swap_accounts.signer && meta.is_signer));
+
+    assert!(s
+        .quote_and_build_exact_in(&quote_params, swap_accounts, 10_001)
+        .is_err());
+}
+
+#[tokio::test]
+async fn other_amount_threshold_sets_slippage_guard() {
+    const IN_AMOUNT: u64 = 1_000_000_000;
+    const OUT_AMOUNT: u64 = 999_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let jupiter_program_id = Pubkey::default();
+    let swap_params = SwapParams {
+        in_amount: IN_AMOUNT,
+        out_amount: OUT_AMOUNT,
+        source_mint: jitosol::ID,
+        destination_mint: msol::ID,
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        token_transfer_authority: Pubkey::new_unique(),
+        open_order_address: None,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &jupiter_program_id,
+        missing_dynamic_accounts_as_default: false,
+    };
+
+    let exact_in_with = |threshold| {
+        BuiltSwap::try_from(
+            s.swap_ix_with_other_amount_threshold(&swap_params, SwapMode::ExactIn, threshold)
+                .unwrap(),
+        )
+        .unwrap()
+        .args
+    };
+    let loose = exact_in_with(OUT_AMOUNT - 10_000_000);
+    let tight = exact_in_with(OUT_AMOUNT - 1_000);
+    assert_eq!(loose.amount, IN_AMOUNT);
+    assert_eq!(loose.min_amount_out, OUT_AMOUNT - 10_000_000);
+    assert_eq!(tight.min_amount_out, OUT_AMOUNT - 1_000);
+    assert_ne!(loose.min_amount_out, tight.min_amount_out);
+
+    let exact_out = s
+        .swap_ix_with_other_amount_threshold(&swap_params, SwapMode::ExactOut, IN_AMOUNT + 1_000)
+        .unwrap();
+    let SwapExactOutIxData(args) = SwapExactOutIxData::deserialize(&exact_out.data).unwrap();
+    assert_eq!(args.amount, OUT_AMOUNT);
+    assert_eq!(args.max_amount_in, IN_AMOUNT + 1_000);
+}
+
+#[tokio::test]
+async fn swap_exact_out_value_calc_accs_serialized() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let jupiter_program_id = Pubkey::default();
+    let ix = s
+        .swap_ix(
+            &SwapParams {
+                in_amount: 0,
+                out_amount: 1_000_000_000,
+                source_mint: jitosol::ID,
+                destination_mint: msol::ID,
+                source_token_account: Pubkey::new_unique(),
+                destination_token_account: Pubkey::new_unique(),
+                token_transfer_authority: Pubkey::new_unique(),
+                open_order_address: None,
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &jupiter_program_id,
+                missing_dynamic_accounts_as_default: false,
+            },
+            SwapMode::ExactOut,
+        )
+        .unwrap();
+    assert_eq!(ix.data[0], s_controller_lib::SWAP_EXACT_OUT_IX_DISCM);
+    let SwapExactOutIxData(args) = SwapExactOutIxData::deserialize(&ix.data).unwrap();
+    let [(_, src_lst_data), (_, dst_lst_data)] =
+        [jitosol::ID, msol::ID].map(|mint| s.find_ready_lst(mint).unwrap());
+    let src_accs = usize::from(args.src_lst_value_calc_accs);
+    let dst_accs = usize::from(args.dst_lst_value_calc_accs);
+    assert_eq!(src_accs, src_lst_data.sol_val_calc.ix_accounts().len());
+    assert_eq!(dst_accs, dst_lst_data.sol_val_calc.ix_accounts().len());
+
+    let pricing_program = try_pool_state(&s.pool_state_data().unwrap())
+        .unwrap()
+        .pricing_program;
+    let src_calc_start = SWAP_EXACT_OUT_IX_ACCOUNTS_LEN;
+    let dst_calc_start = src_calc_start + src_accs;
+    let pricing_start = dst_calc_start + dst_accs;
+    assert_eq!(
+        ix.accounts[src_calc_start].pubkey,
+        src_lst_data.sol_val_calc.sol_value_calculator_program_id()
+    );
+    assert_eq!(
+        ix.accounts[dst_calc_start].pubkey,
+        dst_lst_data.sol_val_calc.sol_value_calculator_program_id()
+    );
+    assert_eq!(ix.accounts[pricing_start].pubkey, pricing_program);
+}
+
+#[tokio::test]
+async fn swap_exact_in_data_matches_expected_args() {
+    const IN_AMOUNT: u64 = 1_000_000_000;
+    const MIN_AMOUNT_OUT: u64 = 999_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let jupiter_program_id = Pubkey::default();
+    let ix = s
+        .swap_ix(
+            &SwapParams {
+                in_amount: IN_AMOUNT,
+                out_amount: MIN_AMOUNT_OUT,
+                source_mint: jitosol::ID,
+                destination_mint: msol::ID,
+                source_token_account: Pubkey::new_unique(),
+                destination_token_account: Pubkey::new_unique(),
+                token_transfer_authority: Pubkey::new_unique(),
+                open_order_address: None,
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &jupiter_program_id,
+                missing_dynamic_accounts_as_default: false,
+            },
+            SwapMode::ExactIn,
+        )
+        .unwrap();
+
+    let lst_state_list = try_lst_state_list(&s.lst_state_list_account.data).unwrap();
+    let [(src_lst_index, src_accs), (dst_lst_index, dst_accs)] =
+        [jitosol::ID, msol::ID].map(|mint| {
+            let (index, _) = try_find_lst_mint_on_list(mint, lst_state_list).unwrap();
+            let (_, lst_data) = s.find_ready_lst(mint).unwrap();
+            (
+                u32::try_from(index).unwrap(),
+                u8::try_from(lst_data.sol_val_calc.ix_accounts().len()).unwrap(),
+            )
+        });
+    // sanity check that the counts are not trivially zero,
+    // which the previous implementation serialized before overwriting them
+    assert!(src_accs > 0 && dst_accs > 0);
+    let expected = SwapExactInIxData(SwapExactInIxArgs {
+        src_lst_value_calc_accs: src_accs,
+        dst_lst_value_calc_accs: dst_accs,
+        src_lst_index,
+        dst_lst_index,
+        min_amount_out: MIN_AMOUNT_OUT,
+        amount: IN_AMOUNT,
+    })
+    .try_to_vec()
+    .unwrap();
+    assert_eq!(ix.data[0], SWAP_EXACT_IN_IX_DISCM);
+    assert_eq!(ix.data, expected);
+}
+
+#[tokio::test]
+async fn sync_and_swap_ixs_syncs_both_lsts_first() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let swap_params = SwapParams {
+        in_amount: 1_000_000_000,
+        out_amount: 0,
+        source_mint: jitosol::ID,
+        destination_mint: msol::ID,
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        token_transfer_authority: Pubkey::new_unique(),
+        open_order_address: None,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &Pubkey::default(),
+        missing_dynamic_accounts_as_default: false,
+    };
+    let mut ixs = s
+        .sync_and_swap_ixs(&swap_params, SwapMode::ExactIn)
+        .unwrap();
+    assert_eq!(ixs.len(), 3);
+
+    let built: BuiltSwap = ixs.pop().unwrap().try_into().unwrap();
+    assert_eq!(built.discriminant, SWAP_EXACT_IN_IX_DISCM);
+    let synced_indexes: Vec<u32> = ixs
+        .iter()
+        .map(|ix| {
+            assert_eq!(ix.program_id, s.program_id);
+            assert_eq!(ix.data[0], SYNC_SOL_VALUE_IX_DISCM);
+            let SyncSolValueIxData(SyncSolValueIxArgs { lst_index }) =
+                SyncSolValueIxData::deserialize(&ix.data).unwrap();
+            lst_index
+        })
+        .collect();
+    assert_eq!(
+        synced_indexes,
+        [built.src_lst_index(), built.dst_lst_index()]
+    );
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use jupiter_amm_interface::{Amm, Quote, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use solana_sdk::{
+    account::{create_account_for_test, Account},
+    clock::Clock,
+    pubkey::Pubkey,
+    sysvar,
+};
+use test_utils::{jitosol, JITO_STAKE_POOL_LAST_UPDATE_EPOCH};
+
+use crate::jito_marinade_basic_amm;
+
+const JITOSOL_TO_MSOL: QuoteParams = QuoteParams {
+    amount: 1_000_000_000,
+    input_mint: jitosol::ID,
+    output_mint: msol::ID,
+    swap_mode: SwapMode::ExactIn,
+};
+
+/// Clock sysvar of the epoch after the one the jito stake pool was last updated for
+fn next_epoch_clock_account_map() -> HashMap<Pubkey, Account> {
+    HashMap::from([(
+        sysvar::clock::ID,
+        create_account_for_test(&Clock {
+            epoch: JITO_STAKE_POOL_LAST_UPDATE_EPOCH + 1,
+            ..Default::default()
+        }),
+    )])
+}
+
+#[tokio::test]
+async fn frozen_clock_quotes_identical_across_epoch_boundary() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.set_clock(Clock {
+        epoch: JITO_STAKE_POOL_LAST_UPDATE_EPOCH,
+        ..Default::default()
+    });
+    let Quote {
+        in_amount: in_before,
+        out_amount: out_before,
+        fee_amount: fee_before,
+        ..
+    } = s.quote(&JITOSOL_TO_MSOL).unwrap();
+
+    s.update(&next_epoch_clock_account_map()).unwrap();
+
+    let Quote {
+        in_amount: in_after,
+        out_amount: out_after,
+        fee_amount: fee_after,
+        ..
+    } = s.quote(&JITOSOL_TO_MSOL).unwrap();
+    assert_eq!(in_before, in_after);
+    assert_eq!(out_before, out_after);
+    assert_eq!(fee_before, fee_after);
+}
+
+#[tokio::test]
+async fn unfrozen_clock_follows_fetched_epoch() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.quote(&JITOSOL_TO_MSOL).unwrap();
+
+    s.update(&next_epoch_clock_account_map()).unwrap();
+
+    // jito stake pool has not been updated for the new epoch
+    assert!(s.quote(&JITOSOL_TO_MSOL).is_err());
+}
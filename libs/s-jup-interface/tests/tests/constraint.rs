@@ -0,0 +1,43 @@
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::QuoteConstraint;
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+fn jitosol_to_msol(amount: u64) -> QuoteParams {
+    QuoteParams {
+        amount,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    }
+}
+
+#[tokio::test]
+async fn quote_constraint_liquidity_names_output_mint() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let params = jitosol_to_msol(1_000_000_000);
+    assert_eq!(s.quote_constraint(&params).unwrap(), QuoteConstraint::Ok);
+
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap()
+        .reserves_balance = Some(1);
+    assert_eq!(
+        s.quote_constraint(&params).unwrap(),
+        QuoteConstraint::Liquidity(msol::ID)
+    );
+}
+
+#[tokio::test]
+async fn quote_constraint_zero_value() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    assert_eq!(
+        s.quote_constraint(&jitosol_to_msol(0)).unwrap(),
+        QuoteConstraint::ZeroValue
+    );
+}
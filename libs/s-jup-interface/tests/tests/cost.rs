@@ -0,0 +1,231 @@
+use flat_fee_test_utils::MockFeeAccountArgs;
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use rust_decimal::Decimal;
+use s_controller_test_utils::{
+    jito_marinade_flat_fee_program_test, JitoMarinadeProgramTestArgs, MockProtocolFeeBps,
+};
+use s_jup_interface::SPoolJup;
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_token_ratio::BPS_DENOMINATOR;
+use solana_sdk::pubkey::Pubkey;
+use test_utils::jitosol;
+
+use crate::{fully_init_amm, jito_marinade_basic_amm, start_jito_marinade, MiscProgramTest};
+
+fn jitosol_to_msol(amount: u64) -> QuoteParams {
+    QuoteParams {
+        amount,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    }
+}
+
+#[tokio::test]
+async fn effective_cost_bps_includes_fees_and_impact() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    for amount in [1_000_000, 100_000_000, 1_000_000_000, 5_000_000_000] {
+        let params = jitosol_to_msol(amount);
+        let fee_pct = s.quote_full(&params).unwrap().fee_pct;
+        let impact = s.price_impact(&params).unwrap();
+        let cost = s.effective_cost_bps(&params).unwrap();
+        assert_eq!(cost, (fee_pct + impact) * Decimal::from(BPS_DENOMINATOR));
+        // 4 bps input + 4 bps output flat fees
+        assert!(cost >= Decimal::from(7u8));
+    }
+}
+
+#[tokio::test]
+async fn price_impact_only_rounding() {
+    // at most a few atomic units of output lost to rounding
+    const MAX_ROUNDING: u8 = 5;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    for amount in [1_000_000, 100_000_000, 1_000_000_000, 5_000_000_000] {
+        let impact = s.price_impact(&jitosol_to_msol(amount)).unwrap();
+        assert!(
+            impact.abs() * Decimal::from(amount) <= Decimal::from(MAX_ROUNDING),
+            "amount: {amount}, impact: {impact}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn effective_cost_bps_does_not_grow_with_size() {
+    // at most a few atomic units of output lost to rounding, in each of fees and price impact
+    const MAX_ROUNDING: u8 = 10;
+    const LARGEST: u64 = 5_000_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let largest_cost = s.effective_cost_bps(&jitosol_to_msol(LARGEST)).unwrap();
+    for amount in [1_000_000, 100_000_000, 1_000_000_000] {
+        let cost = s.effective_cost_bps(&jitosol_to_msol(amount)).unwrap();
+        assert!(
+            (cost - largest_cost).abs() * Decimal::from(amount)
+                <= Decimal::from(MAX_ROUNDING) * Decimal::from(BPS_DENOMINATOR),
+            "amount: {amount}, cost: {cost}, cost at {LARGEST}: {largest_cost}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn effective_cost_bps_exact_out_unsupported() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = QuoteParams {
+        swap_mode: SwapMode::ExactOut,
+        ..jitosol_to_msol(1_000_000_000)
+    };
+    assert!(s.effective_cost_bps(&params).is_err());
+}
+
+async fn jito_marinade_amm_with_fee_bps(fee_bps: i16) -> SPoolJup {
+    let pt = jito_marinade_flat_fee_program_test(
+        JitoMarinadeProgramTestArgs {
+            jitosol_sol_value: 10_000_000_000,
+            msol_sol_value: 10_000_000_000,
+            jitosol_reserves: 10_000_000_000,
+            msol_reserves: 10_000_000_000,
+            jitosol_protocol_fee_accumulator: 0,
+            msol_protocol_fee_accumulator: 0,
+            lp_token_mint: Pubkey::new_unique(),
+            lp_token_supply: 20_000_000_000,
+        },
+        flat_fee_interface::ProgramState {
+            manager: Pubkey::default(),
+            lp_withdrawal_fee_bps: 0,
+        },
+        [
+            MockFeeAccountArgs {
+                input_fee_bps: fee_bps,
+                output_fee_bps: fee_bps,
+                lst_mint: jitosol::ID,
+            },
+            MockFeeAccountArgs {
+                input_fee_bps: fee_bps,
+                output_fee_bps: fee_bps,
+                lst_mint: msol::ID,
+            },
+        ],
+        MockProtocolFeeBps {
+            trading: 1000,
+            lp: 1000,
+        },
+    )
+    .add_s_program();
+    let mut bc = start_jito_marinade(pt).await;
+    fully_init_amm(&mut bc, s_controller_lib::program::ID).await
+}
+
+#[tokio::test]
+async fn max_tolerable_slippage_decreases_as_fees_rise() {
+    let params = jitosol_to_msol(1_000_000_000);
+    let mut prev = u16::MAX;
+    for fee_bps in [0, 4, 100, 1_000, 4_000] {
+        let s = jito_marinade_amm_with_fee_bps(fee_bps).await;
+        let max_bps = s.max_tolerable_slippage_bps(&params).unwrap();
+        assert!(max_bps < prev, "{max_bps} >= {prev} at {fee_bps} bps fees");
+        prev = max_bps;
+    }
+    // 4000 bps input + 4000 bps output: fees exceed the output
+    assert_eq!(prev, 0);
+}
+
+#[tokio::test]
+async fn max_tolerable_slippage_add_liquidity_unsupported() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = QuoteParams {
+        output_mint: s.lp_token_mint().unwrap(),
+        ..jitosol_to_msol(1_000_000_000)
+    };
+    assert!(s.max_tolerable_slippage_bps(&params).is_err());
+}
+
+#[tokio::test]
+async fn fee_breakdown_sums_to_total_deducted() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let (_, jitosol_data) = s.find_ready_lst(jitosol::ID).unwrap();
+    let (_, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    for amount in [1_000_000, 100_000_000, 1_000_000_000, 5_000_000_000] {
+        let params = jitosol_to_msol(amount);
+        let quote = s.quote_full(&params).unwrap();
+        let fees = s.quote_swap_exact_in_fees(&params).unwrap();
+        let no_fee_out_amount = msol_data
+            .sol_val_calc
+            .sol_to_lst(
+                jitosol_data
+                    .sol_val_calc
+                    .lst_to_sol(amount)
+                    .unwrap()
+                    .get_min(),
+            )
+            .unwrap()
+            .get_min();
+        assert_eq!(fees.total(), no_fee_out_amount - quote.out_amount);
+        assert_eq!(fees.lp_fee + fees.protocol_fee, quote.fee_amount);
+        assert!(fees.lp_fee > 0);
+        assert!(fees.protocol_fee > 0);
+    }
+}
+
+#[tokio::test]
+async fn round_trip_loss_bounded_by_fees() {
+    // 4 bps input + 4 bps output flat fees per leg
+    const ROUND_TRIP_FEE_BPS: u64 = 16;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    for amount in [1_000_000, 100_000_000, 1_000_000_000] {
+        let loss = s.round_trip_loss(jitosol::ID, msol::ID, amount).unwrap();
+        assert!(loss > 0);
+        // leeway for rounding in each leg's sol value conversions
+        assert!(loss <= amount * ROUND_TRIP_FEE_BPS / 10_000 + 8);
+    }
+}
+
+#[tokio::test]
+async fn realized_fee_bps_of_recorded_swaps() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    assert!(s.realized_fee_bps().is_none());
+
+    let swaps = [
+        jitosol_to_msol(1_000_000_000),
+        QuoteParams {
+            amount: 3_000_000_000,
+            input_mint: msol::ID,
+            output_mint: jitosol::ID,
+            swap_mode: SwapMode::ExactIn,
+        },
+    ];
+    let sol_value = |s: &SPoolJup, mint, amount| {
+        s.sol_val_calc_for(mint)
+            .unwrap()
+            .lst_to_sol(amount)
+            .unwrap()
+            .get_min()
+    };
+    let (mut volume, mut fees) = (0u64, 0u64);
+    for params in swaps {
+        let quote = s.quote_full(&params).unwrap();
+        volume += sol_value(&s, params.input_mint, quote.in_amount);
+        fees += sol_value(&s, quote.fee_mint, quote.fee_amount);
+        s.record_swap(&params, &quote).unwrap();
+    }
+
+    assert!(fees > 0);
+    assert_eq!(
+        s.realized_fee_bps().unwrap(),
+        Decimal::from(fees) * Decimal::from(BPS_DENOMINATOR) / Decimal::from(volume)
+    );
+}
+
+#[tokio::test]
+async fn record_swap_ignores_allowlist() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let params = jitosol_to_msol(1_000_000_000);
+    let quote = s.quote_full(&params).unwrap();
+
+    // the swap already happened, so it must be recorded even if its LSTs are no longer allowed
+    s.set_allowlist(Some(&[]));
+    s.record_swap(&params, &quote).unwrap();
+    assert!(s.realized_fee_bps().unwrap() > Decimal::ZERO);
+}
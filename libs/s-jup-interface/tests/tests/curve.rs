@@ -0,0 +1,21 @@
+use marinade_keys::msol;
+use s_jup_interface::PRICE_CURVE_CSV_HEADER;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn price_curve_csv_one_row_per_amount() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let amounts = [1_000_000, 1_000_000_000, 5_000_000_000];
+    let csv = s.price_curve_csv(jitosol::ID, msol::ID, &amounts).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next().unwrap(), PRICE_CURVE_CSV_HEADER);
+    let rows: Vec<_> = lines.collect();
+    assert_eq!(rows.len(), amounts.len());
+    for (row, amount) in rows.iter().zip(amounts) {
+        let cols: Vec<_> = row.split(',').collect();
+        assert_eq!(cols.len(), 4);
+        assert_eq!(cols[0], amount.to_string());
+    }
+}
@@ -0,0 +1,33 @@
+use marinade_keys::msol;
+use s_jup_interface::SPoolJup;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn debug_json_basic() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let json = s.to_debug_json();
+    assert_eq!(json["lst_count"], 2);
+    assert_eq!(json["pricing_prog_kind"], "FlatFee");
+    let lsts = json["lsts"].as_array().unwrap();
+    for mint in [jitosol::ID, msol::ID] {
+        let lst = lsts
+            .iter()
+            .find(|lst| lst["mint"] == mint.to_string())
+            .unwrap();
+        assert_eq!(lst["data"]["reserves_balance"], 10_000_000_000u64);
+    }
+    assert_eq!(
+        json["pool_state"]["lp_token_mint"],
+        s.lp_token_mint().unwrap().to_string()
+    );
+}
+
+#[test]
+fn debug_json_unfetched() {
+    let json = SPoolJup::default().to_debug_json();
+    assert_eq!(json["lst_count"], 0);
+    assert!(json["pool_state"].is_null());
+    assert!(json["pricing_prog_kind"].is_null());
+}
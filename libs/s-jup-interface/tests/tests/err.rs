@@ -0,0 +1,59 @@
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_controller_interface::SControllerError;
+use s_jup_interface::{SPoolError, SPoolJup};
+use solana_sdk::pubkey::Pubkey;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[test]
+fn default_pool_state_not_fetched() {
+    let s = SPoolJup::default();
+    assert!(matches!(
+        s.lp_token_mint(),
+        Err(SPoolError::PoolStateNotFetched)
+    ));
+    assert!(matches!(
+        s.pricing_prog(),
+        Err(SPoolError::PricingProgNotFetched)
+    ));
+}
+
+#[tokio::test]
+async fn typed_errors_through_quote_full() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let unknown_mint = Pubkey::new_unique();
+    assert!(matches!(
+        s.find_ready_lst(unknown_mint),
+        Err(SPoolError::LstNotOnList(mint)) if mint == unknown_mint
+    ));
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    assert!(matches!(
+        s.quote_full(&QuoteParams {
+            amount: 1_000_000_000,
+            input_mint: jitosol::ID,
+            output_mint: unknown_mint,
+            swap_mode: SwapMode::ExactIn,
+        }),
+        Err(SPoolError::LstNotOnList(mint)) if mint == unknown_mint
+    ));
+
+    s.pricing_prog = None;
+    let err = s.quote_full(&quote_params).unwrap_err();
+    assert!(matches!(err, SPoolError::PricingProgNotFetched));
+
+    // round trips through anyhow
+    let err: SPoolError = anyhow::Error::from(err).into();
+    assert!(matches!(err, SPoolError::PricingProgNotFetched));
+    let err: SPoolError = anyhow::Error::from(SControllerError::MathError).into();
+    assert!(matches!(
+        err,
+        SPoolError::SController(SControllerError::MathError)
+    ));
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_controller_lib::{find_pool_reserves_address, try_pool_state_mut, FindLstPdaAtaKeys};
+use sanctum_solana_test_utils::{
+    token::{tokenkeg::mock_tokenkeg_account, MockTokenAccountArgs},
+    ExtendedBanksClient, IntoAccount,
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use test_utils::jitosol;
+
+use crate::{jito_marinade_basic_amm, UpdatingSPoolJup};
+
+fn add_liquidity_params(lp_token_mint: Pubkey) -> QuoteParams {
+    QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: lp_token_mint,
+        swap_mode: SwapMode::ExactIn,
+    }
+}
+
+#[tokio::test]
+async fn stale_lp_supply_detected() {
+    let (mut bc, mut s) = jito_marinade_basic_amm().await;
+    let add_liquidity_params = add_liquidity_params(s.lp_token_mint().unwrap());
+    let swap_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    assert_eq!(s.lp_supply_generation(), Some(s.generation()));
+    s.quote_full(&add_liquidity_params).unwrap();
+
+    // pool_state changed, e.g. by an add liquidity, without the LP mint being refetched
+    let mut pool_state_acc = bc.get_account_unwrapped(s.pool_state_addr).await;
+    try_pool_state_mut(&mut pool_state_acc.data)
+        .unwrap()
+        .total_sol_value += 1_000_000_000;
+    let pool_state_only = HashMap::from([(s.pool_state_addr, pool_state_acc)]);
+    let gen_before = s.generation();
+    s.update_full(&pool_state_only).unwrap();
+    assert_eq!(s.generation(), gen_before + 1);
+    assert_eq!(s.lp_supply_generation(), Some(gen_before));
+    assert!(s.fresh_lp_mint_supply().is_err());
+    assert!(s.quote_full(&add_liquidity_params).is_err());
+    // non-LP quotes unaffected
+    s.quote_full(&swap_params).unwrap();
+
+    let s = s.update_with_banks(&mut bc).await;
+    assert_eq!(s.lp_supply_generation(), Some(s.generation()));
+    s.quote_full(&add_liquidity_params).unwrap();
+}
+
+#[tokio::test]
+async fn partial_update_keeps_lp_supply_fresh() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let add_liquidity_params = add_liquidity_params(s.lp_token_mint().unwrap());
+
+    let gen_before = s.generation();
+    s.update_full(&HashMap::<Pubkey, Account>::new()).unwrap();
+    assert_eq!(s.generation(), gen_before + 1);
+    assert_eq!(s.lp_supply_generation(), Some(s.generation()));
+
+    let (msol_reserves, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let reserves_only = HashMap::from([(
+        msol_reserves,
+        mock_tokenkeg_account(MockTokenAccountArgs {
+            mint: msol::ID,
+            authority: s.pool_state_addr,
+            amount: 5_000_000_000,
+        })
+        .into_account(),
+    )]);
+    s.update_full(&reserves_only).unwrap();
+    assert_eq!(s.lp_supply_generation(), Some(s.generation()));
+    s.fresh_lp_mint_supply().unwrap();
+    s.quote_full(&add_liquidity_params).unwrap();
+}
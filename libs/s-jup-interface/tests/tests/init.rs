@@ -0,0 +1,115 @@
+use marinade_keys::{marinade_state, msol};
+use s_jup_interface::{SPoolInitKeys, SPoolJup};
+use s_pricing_prog_aggregate::{KnownPricingProg, MutablePricingProg};
+use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc, MarinadeLstSolValCalc};
+use sanctum_lst_list::SanctumLstList;
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use solana_sdk::{pubkey::Pubkey, sysvar};
+use test_utils::jitosol;
+
+use crate::{
+    jito_marinade_basic_amm, jito_marinade_basic_program_test, start_jito_marinade,
+    UpdatingSPoolJup,
+};
+
+#[tokio::test]
+async fn from_lst_state_list_bytes_matches_account() {
+    let mut bc = start_jito_marinade(jito_marinade_basic_program_test(Pubkey::new_unique())).await;
+    let program_id = s_controller_lib::program::ID;
+    let SPoolInitKeys { lst_state_list, .. } = SPoolJup::init_keys(program_id);
+    let lst_state_list_acc = bc.get_account_unwrapped(lst_state_list).await;
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+
+    let from_bytes = SPoolJup::from_lst_state_list_bytes(
+        program_id,
+        &lst_state_list_acc.data,
+        lst_state_list_acc.owner,
+        &sanctum_lst_list,
+    )
+    .unwrap();
+    let from_account =
+        SPoolJup::from_lst_state_list_account(program_id, lst_state_list_acc, &sanctum_lst_list)
+            .unwrap();
+
+    assert_eq!(
+        from_bytes.lst_state_list_account.data,
+        from_account.lst_state_list_account.data
+    );
+    assert_eq!(
+        from_bytes.get_accounts_to_update_full(),
+        from_account.get_accounts_to_update_full()
+    );
+}
+
+#[tokio::test]
+async fn from_lst_state_list_bytes_wrong_owner() {
+    let mut bc = start_jito_marinade(jito_marinade_basic_program_test(Pubkey::new_unique())).await;
+    let program_id = s_controller_lib::program::ID;
+    let SPoolInitKeys { lst_state_list, .. } = SPoolJup::init_keys(program_id);
+    let lst_state_list_acc = bc.get_account_unwrapped(lst_state_list).await;
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+
+    assert!(SPoolJup::from_lst_state_list_bytes(
+        program_id,
+        &lst_state_list_acc.data,
+        Pubkey::new_unique(),
+        &sanctum_lst_list,
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn ready_to_quote_after_fetching_missing_accounts() {
+    let mut bc = start_jito_marinade(jito_marinade_basic_program_test(Pubkey::new_unique())).await;
+    let program_id = s_controller_lib::program::ID;
+    let SPoolInitKeys {
+        lst_state_list,
+        pool_state,
+    } = SPoolJup::init_keys(program_id);
+    let lst_state_list_acc = bc.get_account_unwrapped(lst_state_list).await;
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+    let s =
+        SPoolJup::from_lst_state_list_account(program_id, lst_state_list_acc, &sanctum_lst_list)
+            .unwrap();
+
+    // pool state, jitoSOL and mSOL reserves and their sol value calculator accounts
+    assert!(!s.is_ready_to_quote());
+    let missing = s.missing_accounts();
+    assert_eq!(missing[0], pool_state);
+    assert!(missing.contains(&marinade_state::ID));
+    assert!(missing.contains(&sysvar::clock::ID));
+    let get_accounts_to_update = s.get_accounts_to_update_full();
+    assert!(missing.iter().all(|pk| get_accounts_to_update.contains(pk)));
+
+    let mut s = s.update_with_banks(&mut bc).await;
+    assert!(s.is_ready_to_quote());
+    assert!(s.missing_accounts().is_empty());
+
+    s.pricing_prog = None;
+    assert!(!s.is_ready_to_quote());
+    assert_eq!(s.missing_accounts(), vec![pool_state]);
+}
+
+#[tokio::test]
+async fn unfetched_calculator_and_pricing_prog_accounts_missing() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    assert!(s.is_ready_to_quote());
+
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap()
+        .sol_val_calc = KnownLstSolValCalc::Marinade(MarinadeLstSolValCalc::default());
+    assert!(!s.is_ready_to_quote());
+    assert_eq!(s.missing_accounts(), vec![marinade_state::ID]);
+
+    let pp = KnownPricingProg::try_new(flat_fee_interface::ID, [jitosol::ID, msol::ID].into_iter())
+        .unwrap();
+    let pricing_prog_accounts = pp.get_accounts_to_update();
+    s.pricing_prog = Some(pp);
+    let missing = s.missing_accounts();
+    assert_eq!(missing.len(), pricing_prog_accounts.len() + 1);
+    assert!(pricing_prog_accounts.iter().all(|pk| missing.contains(pk)));
+    assert!(missing.contains(&marinade_state::ID));
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use jupiter_amm_interface::{Quote, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_controller_interface::SControllerError;
+use s_controller_lib::{try_lst_state_list_mut, U8BoolMut};
+use s_jup_interface::{SPoolError, SPoolJup};
+use solana_sdk::pubkey::Pubkey;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+const AMT: u64 = 1_000_000_000;
+
+fn disable_input(s: &mut SPoolJup, lst_mint: Pubkey) {
+    let lst_state_list =
+        try_lst_state_list_mut(&mut Arc::make_mut(&mut s.lst_state_list_account).data).unwrap();
+    let lst_state = lst_state_list
+        .iter_mut()
+        .find(|lst_state| lst_state.mint == lst_mint)
+        .unwrap();
+    U8BoolMut(&mut lst_state.is_input_disabled).set_true();
+}
+
+fn assert_input_disabled(res: Result<Quote, SPoolError>) {
+    assert!(matches!(
+        res.unwrap_err(),
+        SPoolError::SController(SControllerError::LstInputDisabled)
+    ));
+}
+
+#[tokio::test]
+async fn input_disabled_lst_rejected_as_input() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    disable_input(&mut s, jitosol::ID);
+
+    for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+        assert_input_disabled(s.quote_full(&QuoteParams {
+            amount: AMT,
+            input_mint: jitosol::ID,
+            output_mint: msol::ID,
+            swap_mode,
+        }));
+    }
+    assert_input_disabled(s.quote_full(&QuoteParams {
+        amount: AMT,
+        input_mint: jitosol::ID,
+        output_mint: s.lp_token_mint().unwrap(),
+        swap_mode: SwapMode::ExactIn,
+    }));
+    assert!(matches!(
+        s.find_ready_input_lst(jitosol::ID).unwrap_err(),
+        SPoolError::SController(SControllerError::LstInputDisabled)
+    ));
+}
+
+#[tokio::test]
+async fn input_disabled_lst_still_output() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    disable_input(&mut s, jitosol::ID);
+
+    for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+        s.quote_full(&QuoteParams {
+            amount: AMT,
+            input_mint: msol::ID,
+            output_mint: jitosol::ID,
+            swap_mode,
+        })
+        .unwrap();
+    }
+    s.quote_full(&QuoteParams {
+        amount: AMT,
+        input_mint: s.lp_token_mint().unwrap(),
+        output_mint: jitosol::ID,
+        swap_mode: SwapMode::ExactIn,
+    })
+    .unwrap();
+    s.find_ready_input_lst(msol::ID).unwrap();
+    assert_eq!(s.swappable_pairs(), vec![(msol::ID, jitosol::ID)]);
+}
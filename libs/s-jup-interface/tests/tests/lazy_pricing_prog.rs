@@ -0,0 +1,121 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use jupiter_amm_interface::{Amm, KeyedAccount, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::{SPoolInitKeys, SPoolJup};
+use s_pricing_prog_aggregate::MutablePricingProg;
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn lazy_pricing_prog_fetched_on_first_quote() {
+    let (mut bc, mut s) = jito_marinade_basic_amm().await;
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let expected = s.quote_full(&quote_params).unwrap();
+
+    // prefetch since fetcher is sync
+    let mut pricing_prog_accounts = HashMap::new();
+    for pk in s.pricing_prog().unwrap().get_accounts_to_update() {
+        pricing_prog_accounts.insert(pk, bc.get_account_unwrapped(pk).await);
+    }
+    let was_called = Arc::new(AtomicBool::new(false));
+    let fetcher_was_called = was_called.clone();
+
+    s.pricing_prog = None;
+    assert!(s.quote_full(&quote_params).is_err());
+    let s = s.with_lazy_pricing_prog(Arc::new(
+        move |keys: &[Pubkey]| -> anyhow::Result<HashMap<Pubkey, Account>> {
+            fetcher_was_called.store(true, Ordering::SeqCst);
+            Ok(keys
+                .iter()
+                .filter_map(|pk| pricing_prog_accounts.get(pk).map(|acc| (*pk, acc.clone())))
+                .collect())
+        },
+    ));
+    let quote = s.quote_full(&quote_params).unwrap();
+    assert!(was_called.load(Ordering::SeqCst));
+    assert_eq!(quote.out_amount, expected.out_amount);
+    assert_eq!(quote.fee_amount, expected.fee_amount);
+}
+
+#[tokio::test]
+async fn lazy_pricing_prog_used_until_update_fetches_pricing_prog() {
+    let (mut bc, expected_s) = jito_marinade_basic_amm().await;
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let expected = expected_s.quote_full(&quote_params).unwrap();
+
+    // prefetch since fetcher is sync
+    let pricing_prog_keys = expected_s.get_accounts_to_update_pricing_prog();
+    let mut pricing_prog_accounts = HashMap::new();
+    for pk in pricing_prog_keys.iter() {
+        pricing_prog_accounts.insert(*pk, bc.get_account_unwrapped(*pk).await);
+    }
+    let was_called = Arc::new(AtomicBool::new(false));
+    let fetcher_was_called = was_called.clone();
+
+    let program_id = s_controller_lib::program::ID;
+    let SPoolInitKeys {
+        lst_state_list,
+        pool_state,
+    } = SPoolJup::init_keys(program_id);
+    let lst_state_list_acc = bc.get_account_unwrapped(lst_state_list).await;
+    let mut s = SPoolJup::from_keyed_account(&KeyedAccount {
+        key: lst_state_list,
+        account: lst_state_list_acc.clone(),
+        params: Some(serde_json::Value::String(program_id.to_string())),
+    })
+    .unwrap()
+    .with_lazy_pricing_prog(Arc::new(
+        move |keys: &[Pubkey]| -> anyhow::Result<HashMap<Pubkey, Account>> {
+            fetcher_was_called.store(true, Ordering::SeqCst);
+            Ok(keys
+                .iter()
+                .filter_map(|pk| pricing_prog_accounts.get(pk).map(|acc| (*pk, acc.clone())))
+                .collect())
+        },
+    ));
+
+    let pool_state_acc = bc.get_account_unwrapped(pool_state).await;
+    s.update_full(&HashMap::from([
+        (lst_state_list, lst_state_list_acc),
+        (pool_state, pool_state_acc),
+    ]))
+    .unwrap();
+    assert!(s.pricing_prog.is_some());
+    assert!(!was_called.load(Ordering::SeqCst));
+    assert!(s.pricing_prog().unwrap().missing_accounts().is_empty());
+    assert!(was_called.load(Ordering::SeqCst));
+
+    // fetch everything else, never the pricing program's accounts
+    for _ in 0..2 {
+        let mut fetched = HashMap::new();
+        for pk in s.get_accounts_to_update_full() {
+            if !pricing_prog_keys.contains(&pk) {
+                fetched.insert(pk, bc.get_account_unwrapped(pk).await);
+            }
+        }
+        s.update_full(&fetched).unwrap();
+    }
+    let quote = s.quote_full(&quote_params).unwrap();
+    assert_eq!(quote.out_amount, expected.out_amount);
+    assert_eq!(quote.fee_amount, expected.fee_amount);
+}
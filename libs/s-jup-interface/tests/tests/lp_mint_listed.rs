@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use s_controller_lib::try_pool_state_mut;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn lp_mint_not_listed_ok() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    s.validate_lp_mint_not_listed().unwrap();
+}
+
+#[tokio::test]
+async fn lp_mint_listed_rejected() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let pool_state_acc = Arc::make_mut(s.pool_state_account.as_mut().unwrap());
+    try_pool_state_mut(&mut pool_state_acc.data)
+        .unwrap()
+        .lp_token_mint = jitosol::ID;
+
+    assert!(s.validate_lp_mint_not_listed().is_err());
+    let reserve_mints = s.get_reserve_mints_full();
+    assert_eq!(
+        reserve_mints.iter().filter(|m| **m == jitosol::ID).count(),
+        1
+    );
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use s_controller_interface::LstState;
+use s_controller_lib::try_lst_state_list;
+
+use crate::{jito_marinade_basic_amm, UpdatingSPoolJup};
+
+#[tokio::test]
+async fn extended_lst_state_list_initializes_new_lst() {
+    let (mut bc, mut s) = jito_marinade_basic_amm().await;
+    let full_lst_state_list_data = s.lst_state_list_account.data.clone();
+    let full_lst_state_list = try_lst_state_list(&full_lst_state_list_data).unwrap();
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: full_lst_state_list[0].mint,
+        output_mint: full_lst_state_list[full_lst_state_list.len() - 1].mint,
+        swap_mode: SwapMode::ExactIn,
+    };
+
+    // pretend the last LST was added to the pool after it was initialized
+    Arc::make_mut(&mut s.lst_state_list_account)
+        .data
+        .truncate(full_lst_state_list_data.len() - std::mem::size_of::<LstState>());
+    s.lst_data_list.pop();
+    assert!(s.quote_full(&quote_params).is_err());
+
+    // first update picks up the extended list, second fetches the new LST's accounts
+    let s = s.initial_update(&mut bc).await;
+    assert_eq!(s.lst_data_list.len(), full_lst_state_list.len());
+    assert!(s.lst_data_list.iter().all(Option::is_some));
+    assert!(s.quote_full(&quote_params).unwrap().out_amount > 0);
+}
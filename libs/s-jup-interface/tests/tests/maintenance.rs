@@ -0,0 +1,85 @@
+use s_controller_interface::LstState;
+use s_controller_lib::try_lst_state_list_mut;
+use s_jup_interface::{LstData, SPoolJup};
+use s_sol_val_calc_prog_aggregate::{
+    KnownLstSolValCalc, LstSolValCalc, MarinadeLstSolValCalc, SplLstSolValCalc,
+    SplLstSolValCalcInitKeys, WsolLstSolValCalc,
+};
+use sanctum_lst_list::SanctumLstList;
+use solana_program::program_pack::Pack;
+use solana_sdk::{account::Account, pubkey::Pubkey, rent::Rent};
+
+fn lst_data(sol_val_calc: KnownLstSolValCalc) -> Option<LstData> {
+    Some(LstData {
+        sol_val_calc,
+        reserves_balance: None,
+        token_program: spl_token::ID,
+        reserves_sol_value: None,
+        last_updated_slot: None,
+    })
+}
+
+#[test]
+fn lsts_needing_epoch_update_excludes_wsol() {
+    let spl_mint = Pubkey::new_unique();
+    let marinade = KnownLstSolValCalc::Marinade(MarinadeLstSolValCalc::default());
+    let wsol = KnownLstSolValCalc::Wsol(WsolLstSolValCalc);
+    let s = SPoolJup {
+        lst_data_list: vec![
+            lst_data(wsol.clone()),
+            lst_data(KnownLstSolValCalc::Spl(SplLstSolValCalc::from_keys(
+                SplLstSolValCalcInitKeys {
+                    lst_mint: spl_mint,
+                    stake_pool_addr: Pubkey::new_unique(),
+                },
+            ))),
+            None,
+            lst_data(marinade.clone()),
+        ],
+        ..Default::default()
+    };
+    let res = s.lsts_needing_epoch_update();
+    assert_eq!(res, vec![spl_mint, marinade.lst_mint()]);
+    assert!(!res.contains(&wsol.lst_mint()));
+}
+
+#[test]
+fn reserves_ata_rent_token_2022_higher() {
+    let s = SPoolJup::default();
+    let token_rent = s.reserves_ata_rent(spl_token::ID).unwrap();
+    assert_eq!(
+        token_rent,
+        Rent::default().minimum_balance(spl_token::state::Account::LEN)
+    );
+    assert!(s.reserves_ata_rent(spl_token_2022::ID).unwrap() > token_rent);
+}
+
+#[test]
+fn reserves_ata_rent_unknown_token_program_rejected() {
+    let s = SPoolJup::default();
+    assert!(s.reserves_ata_rent(Pubkey::new_unique()).is_err());
+}
+
+#[test]
+fn zero_calc_lst_pending_config() {
+    let [pending_mint, unknown_calc_mint] = [(); 2].map(|_| Pubkey::new_unique());
+    let mut lst_state_list_account = Account {
+        data: vec![0; 2 * std::mem::size_of::<LstState>()],
+        owner: s_controller_lib::program::ID,
+        ..Default::default()
+    };
+    let lst_state_list = try_lst_state_list_mut(&mut lst_state_list_account.data).unwrap();
+    lst_state_list[0].mint = pending_mint;
+    lst_state_list[1].mint = unknown_calc_mint;
+    lst_state_list[1].sol_value_calculator = Pubkey::new_unique();
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+    let s = SPoolJup::from_lst_state_list_account(
+        s_controller_lib::program::ID,
+        lst_state_list_account,
+        &sanctum_lst_list,
+    )
+    .unwrap();
+
+    assert!(s.lst_data_list.iter().all(Option::is_none));
+    assert_eq!(s.pending_config_lsts(), vec![pending_mint]);
+}
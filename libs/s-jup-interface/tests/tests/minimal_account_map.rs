@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use jupiter_amm_interface::{Amm, Quote, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::SPoolJup;
+use sanctum_lst_list::SanctumLstList;
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use solana_program_test::BanksClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+async fn full_account_map(bc: &mut BanksClient, s: &SPoolJup) -> HashMap<Pubkey, Account> {
+    let mut res = HashMap::new();
+    for pk in s.get_accounts_to_update_full() {
+        res.insert(pk, bc.get_account_unwrapped(pk).await);
+    }
+    res
+}
+
+fn assert_minimal_map_reproduces_quote(
+    s: &SPoolJup,
+    full: &HashMap<Pubkey, Account>,
+    quote_params: &QuoteParams,
+) {
+    let minimal = s.minimal_account_map_for(quote_params, full).unwrap();
+    assert!(minimal.len() < full.len());
+
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+    let mut from_minimal = SPoolJup::from_lst_state_list_account(
+        s.program_id,
+        minimal.get(&s.lst_state_list_addr).unwrap().clone(),
+        &sanctum_lst_list,
+    )
+    .unwrap();
+    // 2x update: pricing program is only initialized after fetching pool state
+    from_minimal.update(&minimal).unwrap();
+    from_minimal.update(&minimal).unwrap();
+
+    let Quote {
+        in_amount,
+        out_amount,
+        fee_amount,
+        ..
+    } = s.quote(quote_params).unwrap();
+    let Quote {
+        in_amount: minimal_in_amount,
+        out_amount: minimal_out_amount,
+        fee_amount: minimal_fee_amount,
+        ..
+    } = from_minimal.quote(quote_params).unwrap();
+    assert_eq!(in_amount, minimal_in_amount);
+    assert_eq!(out_amount, minimal_out_amount);
+    assert_eq!(fee_amount, minimal_fee_amount);
+}
+
+#[tokio::test]
+async fn minimal_account_map_reproduces_swap_quote() {
+    let (mut bc, s) = jito_marinade_basic_amm().await;
+    let full = full_account_map(&mut bc, &s).await;
+    for swap_mode in [SwapMode::ExactIn, SwapMode::ExactOut] {
+        assert_minimal_map_reproduces_quote(
+            &s,
+            &full,
+            &QuoteParams {
+                amount: 1_000_000_000,
+                input_mint: jitosol::ID,
+                output_mint: msol::ID,
+                swap_mode,
+            },
+        );
+    }
+}
+
+#[tokio::test]
+async fn minimal_account_map_reproduces_liquidity_quotes() {
+    let (mut bc, s) = jito_marinade_basic_amm().await;
+    let full = full_account_map(&mut bc, &s).await;
+    let lp_token_mint = s.lp_token_mint().unwrap();
+    for (input_mint, output_mint) in [(jitosol::ID, lp_token_mint), (lp_token_mint, msol::ID)] {
+        assert_minimal_map_reproduces_quote(
+            &s,
+            &full,
+            &QuoteParams {
+                amount: 1_000_000_000,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactIn,
+            },
+        );
+    }
+}
+
+#[tokio::test]
+async fn minimal_account_map_missing_account() {
+    let (mut bc, s) = jito_marinade_basic_amm().await;
+    let mut full = full_account_map(&mut bc, &s).await;
+    let (lst_state, lst_data) = s.find_ready_lst(msol::ID).unwrap();
+    full.remove(&s.pool_reserves_account(&lst_state, lst_data).unwrap());
+
+    assert!(s
+        .minimal_account_map_for(
+            &QuoteParams {
+                amount: 1_000_000_000,
+                input_mint: jitosol::ID,
+                output_mint: msol::ID,
+                swap_mode: SwapMode::ExactIn,
+            },
+            &full,
+        )
+        .is_err());
+}
@@ -1,7 +1,40 @@
 //! TODO: test pricing program changed
 //! TODO: test lst state list changed
 
+mod account_limit;
+mod accounts;
 mod add_liquidity;
+mod allowlist;
+mod amm;
+mod built_swap;
+mod clock;
+mod constraint;
+mod cost;
+mod curve;
+mod debug;
+mod err;
+mod generation;
+mod init;
+mod input_disabled;
+mod lazy_pricing_prog;
+mod lp_mint_listed;
+mod lst_state_list_update;
+mod maintenance;
+mod minimal_account_map;
+mod on_update;
+mod pairs;
+mod partial_update;
+mod pricing_paused;
+mod pricing_prog_account;
 mod remove_liquidity;
+mod reserves_override;
+mod shadow;
+mod snapshot;
 mod swap_exact_in;
 mod swap_exact_out;
+mod synthetic;
+mod unlisted_spl;
+mod validation;
+mod valuation;
+mod view;
+mod wsol;
@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use jupiter_amm_interface::Amm;
+use marinade_keys::msol;
+use s_controller_lib::{find_pool_reserves_address, FindLstPdaAtaKeys};
+use s_jup_interface::UpdateSummary;
+use sanctum_solana_test_utils::{
+    token::{tokenkeg::mock_tokenkeg_account, MockTokenAccountArgs},
+    IntoAccount,
+};
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn on_update_fires_with_changed_reserves() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let summaries: Arc<Mutex<Vec<UpdateSummary>>> = Arc::default();
+    let captured = summaries.clone();
+    s.set_on_update(Box::new(move |summary: &UpdateSummary| {
+        captured.lock().unwrap().push(summary.clone())
+    }));
+
+    let (msol_reserves, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let changed = HashMap::from([(
+        msol_reserves,
+        mock_tokenkeg_account(MockTokenAccountArgs {
+            mint: msol::ID,
+            authority: s.pool_state_addr,
+            amount: 1_000_000_000,
+        })
+        .into_account(),
+    )]);
+    s.update(&changed).unwrap();
+
+    let summaries = summaries.lock().unwrap();
+    assert_eq!(
+        *summaries,
+        vec![UpdateSummary {
+            generation: s.generation(),
+            changed_reserves: vec![msol::ID],
+        }]
+    );
+}
@@ -0,0 +1,151 @@
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::LstData;
+use s_sol_val_calc_prog_aggregate::{
+    KnownLstSolValCalc, LstSolValCalc, LstSolValCalcKind, WsolLstSolValCalc,
+};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn swappable_pairs_basic() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let mut pairs = s.swappable_pairs();
+    pairs.sort();
+    let mut expected = vec![(jitosol::ID, msol::ID), (msol::ID, jitosol::ID)];
+    expected.sort();
+    assert_eq!(pairs, expected);
+}
+
+#[tokio::test]
+async fn max_output_excludes_protocol_fee() {
+    const MSOL_RESERVES: u64 = 10_000_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let max = s.max_output(jitosol::ID, msol::ID).unwrap();
+    // 10% of the 8 bps fee goes to the protocol fee accumulator
+    assert!(max < MSOL_RESERVES);
+    assert!(max > MSOL_RESERVES - MSOL_RESERVES / 10_000);
+
+    let trace = s
+        .quote_swap_exact_in_trace(&QuoteParams {
+            amount: 1_000_000_000,
+            input_mint: jitosol::ID,
+            output_mint: msol::ID,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    let expected = u128::from(MSOL_RESERVES) * u128::from(trace.dst_lst_out)
+        / u128::from(trace.dst_lst_out + trace.to_protocol_fees_lst_amount);
+    assert!(u128::from(max).abs_diff(expected) <= 1);
+}
+
+#[tokio::test]
+async fn unfetched_reserves_lst_excluded() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap()
+        .reserves_balance = None;
+
+    assert!(s.swappable_pairs().is_empty());
+    assert!(s.max_output(jitosol::ID, msol::ID).is_err());
+}
+
+#[tokio::test]
+async fn zero_reserve_lst_only_input() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap()
+        .reserves_balance = Some(0);
+
+    assert_eq!(s.swappable_pairs(), vec![(msol::ID, jitosol::ID)]);
+    assert!(s.max_output(jitosol::ID, msol::ID).is_err());
+    assert!(s.max_output(msol::ID, jitosol::ID).is_ok());
+}
+
+#[tokio::test]
+async fn output_liquidity_map_matches_max_output() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap()
+        .reserves_balance = Some(0);
+
+    let map = s.output_liquidity_map();
+    assert_eq!(map.len(), 1);
+    assert_eq!(
+        map[&jitosol::ID],
+        s.max_output(msol::ID, jitosol::ID).unwrap()
+    );
+    assert!(!map.contains_key(&msol::ID));
+
+    let by_pair = s.output_liquidity_map_by_pair();
+    assert_eq!(by_pair.len(), 1);
+    assert_eq!(by_pair[&(msol::ID, jitosol::ID)], map[&jitosol::ID]);
+    assert!(!by_pair.contains_key(&(jitosol::ID, msol::ID)));
+}
+
+#[tokio::test]
+async fn output_liquidity_map_per_mint() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let map = s.output_liquidity_map();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&msol::ID], s.max_output(jitosol::ID, msol::ID).unwrap());
+    assert_eq!(
+        map[&jitosol::ID],
+        s.max_output(msol::ID, jitosol::ID).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn quote_all_pairs_matches_individual_quotes() {
+    const AMT: u64 = 1_000_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let all = s.quote_all_pairs(AMT);
+    assert_eq!(all.len(), 2);
+    for ((input_mint, output_mint), quote) in all {
+        let expected = s
+            .quote_full(&QuoteParams {
+                amount: AMT,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactIn,
+            })
+            .unwrap();
+        let quote = quote.unwrap();
+        assert_eq!(quote.out_amount, expected.out_amount);
+        assert_eq!(quote.fee_amount, expected.fee_amount);
+        assert_eq!(quote.fee_pct, expected.fee_pct);
+        assert_eq!(quote.not_enough_liquidity, expected.not_enough_liquidity);
+    }
+}
+
+#[tokio::test]
+async fn lsts_grouped_by_calc_variant() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.lst_data_list.push(Some(LstData {
+        sol_val_calc: KnownLstSolValCalc::Wsol(WsolLstSolValCalc),
+        reserves_balance: None,
+        token_program: spl_token::ID,
+        reserves_sol_value: None,
+        last_updated_slot: None,
+    }));
+    let by_kind = s.lsts_by_calc_variant();
+    assert_eq!(by_kind[&LstSolValCalcKind::Marinade], vec![msol::ID]);
+    assert_eq!(
+        by_kind[&LstSolValCalcKind::Wsol],
+        vec![spl_token::native_mint::ID]
+    );
+    assert_eq!(by_kind.values().map(Vec::len).sum::<usize>(), 3);
+    assert!(by_kind.values().flatten().any(|mint| *mint == jitosol::ID));
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use jupiter_amm_interface::Amm;
+use marinade_keys::msol;
+use s_controller_lib::{find_pool_reserves_address, FindLstPdaAtaKeys};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_solana_test_utils::{
+    token::{tokenkeg::mock_tokenkeg_account, MockTokenAccountArgs},
+    ExtendedBanksClient, IntoAccount,
+};
+use solana_sdk::account::Account;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn single_account_update_skips_untouched_lsts() {
+    const NEW_RESERVES: u64 = 5_000_000_000;
+    // not a real sol value, only recomputed if jitoSOL's calculator is updated
+    const SENTINEL_SOL_VALUE: u64 = 1;
+
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let jitosol_data = s
+        .lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == jitosol::ID)
+        .unwrap();
+    let jitosol_reserves = jitosol_data.reserves_balance.unwrap();
+    jitosol_data.reserves_sol_value = Some((jitosol_reserves, SENTINEL_SOL_VALUE));
+    let lst_state_list_ptr = s.lst_state_list_account.data.as_ptr();
+    let pool_state_ptr = s.pool_state_account.as_ref().unwrap().data.as_ptr();
+
+    let (msol_reserves, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let single = HashMap::from([(
+        msol_reserves,
+        mock_tokenkeg_account(MockTokenAccountArgs {
+            mint: msol::ID,
+            authority: s.pool_state_addr,
+            amount: NEW_RESERVES,
+        })
+        .into_account(),
+    )]);
+    s.update(&single).unwrap();
+
+    let (_, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    assert_eq!(msol_data.reserves_balance, Some(NEW_RESERVES));
+    assert_eq!(
+        msol_data.reserves_sol_value,
+        Some((
+            NEW_RESERVES,
+            msol_data
+                .sol_val_calc
+                .lst_to_sol(NEW_RESERVES)
+                .unwrap()
+                .get_min()
+        ))
+    );
+    let (_, jitosol_data) = s.find_ready_lst(jitosol::ID).unwrap();
+    assert_eq!(
+        jitosol_data.reserves_sol_value,
+        Some((jitosol_reserves, SENTINEL_SOL_VALUE))
+    );
+    assert_eq!(s.lst_state_list_account.data.as_ptr(), lst_state_list_ptr);
+    assert_eq!(
+        s.pool_state_account.as_ref().unwrap().data.as_ptr(),
+        pool_state_ptr
+    );
+}
+
+#[tokio::test]
+async fn unchanged_accounts_not_recloned() {
+    let (mut bc, mut s) = jito_marinade_basic_amm().await;
+    let mut full: HashMap<_, Account> = HashMap::new();
+    for pk in s.get_accounts_to_update() {
+        full.insert(pk, bc.get_account_unwrapped(pk).await);
+    }
+    s.update(&full).unwrap();
+    let lst_state_list_ptr = s.lst_state_list_account.data.as_ptr();
+    let pool_state_ptr = s.pool_state_account.as_ref().unwrap().data.as_ptr();
+
+    s.update(&full).unwrap();
+
+    assert_eq!(s.lst_state_list_account.data.as_ptr(), lst_state_list_ptr);
+    assert_eq!(
+        s.pool_state_account.as_ref().unwrap().data.as_ptr(),
+        pool_state_ptr
+    );
+}
+
+#[tokio::test]
+async fn update_at_slot_records_reserves_slot() {
+    const SLOT: u64 = 1_000;
+    const AGE: u64 = 5;
+
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    assert!(s.staleness(SLOT).is_empty());
+
+    let (msol_reserves, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let single = HashMap::from([(
+        msol_reserves,
+        mock_tokenkeg_account(MockTokenAccountArgs {
+            mint: msol::ID,
+            authority: s.pool_state_addr,
+            amount: 1_000_000_000,
+        })
+        .into_account(),
+    )]);
+    s.update_full_at_slot(&single, SLOT).unwrap();
+
+    assert_eq!(s.staleness(SLOT + AGE), vec![(msol::ID, AGE)]);
+    let (_, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    assert_eq!(msol_data.last_updated_slot, Some(SLOT));
+}
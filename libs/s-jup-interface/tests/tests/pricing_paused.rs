@@ -0,0 +1,84 @@
+use flat_fee_test_utils::MockFeeAccountArgs;
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_controller_test_utils::{
+    jito_marinade_flat_fee_program_test, JitoMarinadeProgramTestArgs, MockProtocolFeeBps,
+};
+use s_jup_interface::SPoolError;
+use s_pricing_prog_aggregate::PricingProgErr;
+use solana_sdk::pubkey::Pubkey;
+use test_utils::jitosol;
+
+use crate::{fully_init_amm, start_jito_marinade, MiscProgramTest};
+
+#[tokio::test]
+async fn flat_fee_100_pct_fees_is_pricing_paused() {
+    let pt = jito_marinade_flat_fee_program_test(
+        JitoMarinadeProgramTestArgs {
+            jitosol_sol_value: 10_000_000_000,
+            msol_sol_value: 10_000_000_000,
+            jitosol_reserves: 10_000_000_000,
+            msol_reserves: 10_000_000_000,
+            jitosol_protocol_fee_accumulator: 0,
+            msol_protocol_fee_accumulator: 0,
+            lp_token_mint: Pubkey::new_unique(),
+            lp_token_supply: 20_000_000_000,
+        },
+        flat_fee_interface::ProgramState {
+            manager: Pubkey::default(),
+            lp_withdrawal_fee_bps: 0,
+        },
+        [
+            MockFeeAccountArgs {
+                input_fee_bps: 5_000,
+                output_fee_bps: 4,
+                lst_mint: jitosol::ID,
+            },
+            MockFeeAccountArgs {
+                input_fee_bps: 4,
+                output_fee_bps: 5_000,
+                lst_mint: msol::ID,
+            },
+        ],
+        MockProtocolFeeBps {
+            trading: 1000,
+            lp: 1000,
+        },
+    )
+    .add_s_program();
+    let mut bc = start_jito_marinade(pt).await;
+    let s = fully_init_amm(&mut bc, s_controller_lib::program::ID).await;
+
+    let quote_params = |input_mint, output_mint| QuoteParams {
+        amount: 1_000_000_000,
+        input_mint,
+        output_mint,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let err = s
+        .quote_full(&quote_params(jitosol::ID, msol::ID))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SPoolError::PricingProg(PricingProgErr::PricingPaused)
+    ));
+    let err = Amm::quote(&s, &quote_params(jitosol::ID, msol::ID)).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<PricingProgErr>(),
+        Some(&PricingProgErr::PricingPaused)
+    );
+    let err = s
+        .to_view()
+        .quote(&quote_params(jitosol::ID, msol::ID))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<PricingProgErr>(),
+        Some(&PricingProgErr::PricingPaused)
+    );
+    assert!(
+        s.quote_full(&quote_params(msol::ID, jitosol::ID))
+            .unwrap()
+            .out_amount
+            > 0
+    );
+}
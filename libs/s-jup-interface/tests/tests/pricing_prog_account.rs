@@ -0,0 +1,122 @@
+use flat_fee_test_utils::{MockFeeAccount, MockFeeAccountArgs};
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use pricing_programs_interface::{PriceExactInIxArgs, PriceExactInKeys};
+use s_jup_interface::{SPoolError, SPoolJup};
+use s_pricing_prog_aggregate::{PricingProg, PricingProgErr, PricingProgKind};
+use sanctum_solana_test_utils::IntoAccount;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn single_pricing_prog_account_update_reflected_in_quotes() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    s.quote_full(&quote_params).unwrap();
+
+    assert!(!s
+        .update_pricing_prog_account(Pubkey::new_unique(), Account::default())
+        .unwrap());
+
+    let pricing_program_id = s.pricing_prog().unwrap().pricing_program_id();
+    let (fee_account, addr) = MockFeeAccountArgs {
+        input_fee_bps: 10_000,
+        output_fee_bps: 4,
+        lst_mint: jitosol::ID,
+    }
+    .to_fee_account_and_addr(pricing_program_id);
+    assert!(s
+        .update_pricing_prog_account(addr, MockFeeAccount(fee_account).into_account())
+        .unwrap());
+
+    assert!(matches!(
+        s.quote_full(&quote_params),
+        Err(SPoolError::PricingProg(PricingProgErr::PricingPaused))
+    ));
+}
+
+const SOL_VALUE: u64 = 1_000_000_000;
+
+fn price_exact_in(
+    s: &SPoolJup,
+    input_lst_mint: Pubkey,
+    output_lst_mint: Pubkey,
+) -> anyhow::Result<u64> {
+    s.pricing_prog().unwrap().quote_exact_in(
+        PriceExactInKeys {
+            input_lst_mint,
+            output_lst_mint,
+        },
+        &PriceExactInIxArgs {
+            amount: SOL_VALUE,
+            sol_value: SOL_VALUE,
+        },
+    )
+}
+
+#[tokio::test]
+async fn per_lst_output_fee_applies_to_that_lst_only() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    // 4 bps input + 4 bps output
+    assert_eq!(
+        price_exact_in(&s, jitosol::ID, msol::ID).unwrap(),
+        SOL_VALUE * (10_000 - 8) / 10_000
+    );
+
+    let pricing_program_id = s.pricing_prog().unwrap().pricing_program_id();
+    let (fee_account, addr) = MockFeeAccountArgs {
+        input_fee_bps: 4,
+        output_fee_bps: 50,
+        lst_mint: msol::ID,
+    }
+    .to_fee_account_and_addr(pricing_program_id);
+    s.update_pricing_prog_account(addr, MockFeeAccount(fee_account).into_account())
+        .unwrap();
+
+    assert_eq!(
+        price_exact_in(&s, jitosol::ID, msol::ID).unwrap(),
+        SOL_VALUE * (10_000 - 54) / 10_000
+    );
+    assert_eq!(
+        price_exact_in(&s, msol::ID, jitosol::ID).unwrap(),
+        SOL_VALUE * (10_000 - 8) / 10_000
+    );
+}
+
+#[tokio::test]
+async fn lst_without_fee_account_not_priced() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let no_fee_account_mint = Pubkey::new_unique();
+    // the flat fee program requires the LST's FeeAccount, there is no default fee to fall back to
+    assert!(price_exact_in(&s, jitosol::ID, no_fee_account_mint).is_err());
+    assert!(price_exact_in(&s, no_fee_account_mint, msol::ID).is_err());
+
+    let pricing_program_id = s.pricing_prog().unwrap().pricing_program_id();
+    let (_fee_account, addr) = MockFeeAccountArgs {
+        input_fee_bps: 0,
+        output_fee_bps: 0,
+        lst_mint: no_fee_account_mint,
+    }
+    .to_fee_account_and_addr(pricing_program_id);
+    assert!(s
+        .get_accounts_to_update_pricing_prog_for_lsts([no_fee_account_mint].into_iter())
+        .contains(&addr));
+}
+
+#[tokio::test]
+async fn pricing_prog_kind_read_from_pool_state() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    assert_eq!(s.pricing_prog_kind().unwrap(), PricingProgKind::FlatFee);
+
+    // does not need the pricing program itself
+    s.pricing_prog = None;
+    assert!(s.pricing_prog().is_err());
+    assert_eq!(s.pricing_prog_kind().unwrap(), PricingProgKind::FlatFee);
+}
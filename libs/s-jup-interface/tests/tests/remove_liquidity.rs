@@ -1,16 +1,25 @@
 use flat_fee_test_utils::MockFeeAccountArgs;
 use jupiter_amm_interface::{QuoteParams, SwapMode};
 use marinade_keys::msol;
+use s_controller_interface::RemoveLiquidityIxData;
+use s_controller_lib::{
+    find_protocol_fee_accumulator_address,
+    program::{LST_STATE_LIST_ID, POOL_STATE_ID},
+    remove_liquidity_ix_by_mint_full, try_lst_state_list, AddRemoveLiquidityAccountSuffixes,
+    FindLstPdaAtaKeys, RemoveLiquidityByMintFreeArgs, RemoveLiquidityIxAmts,
+};
 use s_controller_test_utils::{
     jito_marinade_flat_fee_program_test, JitoMarinadeProgramTestArgs, MockProtocolFeeBps,
 };
+use s_pricing_prog_aggregate::{KnownPricingProg, PricingProg};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
 use sanctum_solana_test_utils::ExtendedProgramTest;
 use sanctum_token_lib::MintWithTokenProgram;
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer};
 use test_utils::{jitosol, JITO_STAKE_POOL_LAST_UPDATE_EPOCH};
 
-use crate::{assert_quote_swap_eq, fully_init_amm, MiscProgramTest};
+use crate::{assert_quote_swap_eq, fully_init_amm, jito_marinade_basic_amm, MiscProgramTest};
 
 #[tokio::test]
 async fn remove_liquidity_jito_basic() {
@@ -92,3 +101,111 @@ async fn remove_liquidity_jito_basic() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn add_then_remove_liquidity_round_trip() {
+    const AMT: u64 = 1_000_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let lp_out = s.quote_add_liquidity(jitosol::ID, AMT).unwrap().out_amount;
+    let quote = s.quote_remove_liquidity(jitosol::ID, lp_out).unwrap();
+    assert!(!quote.not_enough_liquidity);
+    assert_eq!(quote.in_amount, lp_out);
+    assert_eq!(quote.fee_mint, jitosol::ID);
+    // only loses the fees charged on the way in and out
+    assert!(quote.out_amount < AMT);
+    assert!(quote.out_amount > AMT * 99 / 100);
+}
+
+#[tokio::test]
+async fn remove_liquidity_not_enough_reserves() {
+    const LP_AMT: u64 = 1_000_000_000;
+
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    assert!(
+        !s.quote_remove_liquidity(jitosol::ID, LP_AMT)
+            .unwrap()
+            .not_enough_liquidity
+    );
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == jitosol::ID)
+        .unwrap()
+        .reserves_balance = Some(1);
+    assert!(
+        s.quote_remove_liquidity(jitosol::ID, LP_AMT)
+            .unwrap()
+            .not_enough_liquidity
+    );
+}
+
+#[tokio::test]
+async fn remove_liquidity_zero_supply() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    s.lp_mint_supply = Some(0);
+    assert!(s
+        .quote_remove_liquidity(jitosol::ID, 1_000_000_000)
+        .is_err());
+}
+
+#[tokio::test]
+async fn remove_liquidity_by_mint_resolves_pool_and_pricing_accounts() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let pool_state = s.pool_state_account.as_deref().unwrap();
+    let lst_state_list = try_lst_state_list(&s.lst_state_list_account.data).unwrap();
+    let pricing_prog = s.pricing_prog().unwrap();
+    let KnownPricingProg::FlatFee(flat_fee) = pricing_prog;
+    let pricing_program_state = flat_fee.find_program_state_addr();
+    let free_args = |mint| RemoveLiquidityByMintFreeArgs {
+        signer: Pubkey::new_unique(),
+        src_lp_acc: Pubkey::new_unique(),
+        dst_lst_acc: Pubkey::new_unique(),
+        pool_state,
+        lst_state_list: &*s.lst_state_list_account,
+        lst_mint: MintWithTokenProgram {
+            pubkey: mint,
+            token_program: spl_token::ID,
+        },
+    };
+    for mint in [jitosol::ID, msol::ID] {
+        let expected_index = lst_state_list
+            .iter()
+            .position(|lst_state| lst_state.mint == mint)
+            .unwrap();
+        let (keys, lst_index, _program_ids) = free_args(mint).resolve().unwrap();
+        let (protocol_fee_accumulator, _bump) =
+            find_protocol_fee_accumulator_address(FindLstPdaAtaKeys {
+                lst_mint: mint,
+                token_program: spl_token::ID,
+            });
+        assert_eq!(lst_index, expected_index);
+        assert_eq!(keys.pool_state, POOL_STATE_ID);
+        assert_eq!(keys.lst_state_list, LST_STATE_LIST_ID);
+        assert_eq!(keys.lp_token_mint, s.lp_token_mint().unwrap());
+        assert_eq!(keys.protocol_fee_accumulator, protocol_fee_accumulator);
+
+        let (_, lst_data) = s.find_ready_lst(mint).unwrap();
+        let lst_calculator_accounts = lst_data.sol_val_calc.ix_accounts();
+        let pricing_program_price_lp_accounts = pricing_prog
+            .price_lp_tokens_to_redeem_accounts(mint)
+            .unwrap();
+        let ix = remove_liquidity_ix_by_mint_full(
+            free_args(mint),
+            RemoveLiquidityIxAmts {
+                lp_token_amount: 1_000_000_000,
+                min_lst_out: 0,
+            },
+            AddRemoveLiquidityAccountSuffixes {
+                lst_calculator_accounts: &lst_calculator_accounts,
+                pricing_program_price_lp_accounts: &pricing_program_price_lp_accounts,
+            },
+        )
+        .unwrap();
+        let RemoveLiquidityIxData(args) = RemoveLiquidityIxData::deserialize(&ix.data).unwrap();
+        assert_eq!(args.lst_index, expected_index as u32);
+        for expected in [pricing_prog.pricing_program_id(), pricing_program_state] {
+            assert!(ix.accounts.iter().any(|meta| meta.pubkey == expected));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use marinade_keys::msol;
+use s_controller_lib::{find_pool_reserves_address, FindLstPdaAtaKeys};
+use s_jup_interface::SPoolJup;
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_solana_test_utils::{
+    token::{tokenkeg::mock_tokenkeg_account, MockTokenAccountArgs},
+    ExtendedBanksClient, IntoAccount,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn overridden_reserves_account_fetched_instead_of_ata() {
+    const OVERRIDE_BALANCE: u64 = 123_456_789;
+
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let (msol_reserves_ata, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let override_addr = Pubkey::new_unique();
+    s.set_reserves_account(msol::ID, override_addr);
+
+    let accounts = s.get_accounts_to_update_full();
+    assert!(accounts.contains(&override_addr));
+    assert!(!accounts.contains(&msol_reserves_ata));
+
+    let override_acc = mock_tokenkeg_account(MockTokenAccountArgs {
+        mint: msol::ID,
+        authority: s.pool_state_addr,
+        amount: OVERRIDE_BALANCE,
+    })
+    .into_account();
+    s.update_lst_data_list(&HashMap::from([(override_addr, override_acc)]))
+        .unwrap();
+    let (_, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    assert_eq!(msol_data.reserves_balance, Some(OVERRIDE_BALANCE));
+}
+
+#[tokio::test]
+async fn instructions_use_reserves_ata_despite_override() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let (msol_reserves_ata, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let override_addr = Pubkey::new_unique();
+    s.set_reserves_account(msol::ID, override_addr);
+
+    let ix = s.sync_sol_value_ix_by_mint(msol::ID).unwrap();
+    assert!(ix.accounts.iter().any(|m| m.pubkey == msol_reserves_ata));
+    assert!(ix.accounts.iter().all(|m| m.pubkey != override_addr));
+}
+
+#[tokio::test]
+async fn stale_token_program_corrected_from_reserves_owner() {
+    let (mut bc, mut s) = jito_marinade_basic_amm().await;
+    let (msol_reserves_ata, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let expected_balance = s.find_ready_lst(msol::ID).unwrap().1.reserves_balance;
+    // pin the reserves address so that it does not depend on the stale token program
+    s.set_reserves_account(msol::ID, msol_reserves_ata);
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap()
+        .token_program = spl_token_2022::ID;
+
+    let reserves = bc.get_account_unwrapped(msol_reserves_ata).await;
+    s.update_lst_data_list(&HashMap::from([(msol_reserves_ata, reserves)]))
+        .unwrap();
+
+    let (lst_state, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    assert_eq!(msol_data.token_program, spl_token::ID);
+    assert_eq!(msol_data.reserves_balance, expected_balance);
+    assert_eq!(
+        SPoolJup::reserves_ata(
+            s.pool_state_addr,
+            msol::ID,
+            msol_data.token_program,
+            lst_state.pool_reserves_bump
+        )
+        .unwrap(),
+        msol_reserves_ata
+    );
+}
+
+#[tokio::test]
+async fn non_token_program_reserves_owner_rejected() {
+    let (mut bc, mut s) = jito_marinade_basic_amm().await;
+    let (msol_reserves_ata, _bump) = find_pool_reserves_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    let mut reserves = bc.get_account_unwrapped(msol_reserves_ata).await;
+    reserves.owner = Pubkey::new_unique();
+
+    assert!(s
+        .update_lst_data_list(&HashMap::from([(msol_reserves_ata, reserves)]))
+        .is_err());
+    let (_, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    assert_eq!(msol_data.token_program, spl_token::ID);
+}
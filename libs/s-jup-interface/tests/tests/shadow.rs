@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_sol_val_calc_prog_aggregate::KnownLstSolValCalc;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+const JITOSOL_TO_MSOL: QuoteParams = QuoteParams {
+    amount: 1_000_000_000,
+    input_mint: jitosol::ID,
+    output_mint: msol::ID,
+    swap_mode: SwapMode::ExactIn,
+};
+
+#[tokio::test]
+async fn shadow_quote_differs_with_different_rate() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let quote = s.quote_full(&JITOSOL_TO_MSOL).unwrap();
+
+    let current_calc = s.sol_val_calc_for(jitosol::ID).unwrap().clone();
+    let same = s
+        .quote_with_shadow_calcs(
+            &JITOSOL_TO_MSOL,
+            &HashMap::from([(jitosol::ID, current_calc.clone())]),
+        )
+        .unwrap();
+    assert_eq!(same.out_amount, quote.out_amount);
+
+    let mut doubled_rate = current_calc;
+    let KnownLstSolValCalc::Spl(spl) = &mut doubled_rate else {
+        panic!("jitoSOL not an SPL LST");
+    };
+    spl.calc.as_mut().unwrap().total_lamports *= 2;
+    let shadow = s
+        .quote_with_shadow_calcs(
+            &JITOSOL_TO_MSOL,
+            &HashMap::from([(jitosol::ID, doubled_rate.clone())]),
+        )
+        .unwrap();
+    assert!(shadow.out_amount > quote.out_amount);
+    // original unchanged
+    assert_eq!(
+        s.quote_full(&JITOSOL_TO_MSOL).unwrap().out_amount,
+        quote.out_amount
+    );
+
+    // keyed by the wrong mint
+    assert!(s
+        .quote_with_shadow_calcs(&JITOSOL_TO_MSOL, &HashMap::from([(msol::ID, doubled_rate)]))
+        .is_err());
+}
@@ -0,0 +1,166 @@
+use jupiter_amm_interface::{Quote, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::{SPoolInitKeys, SPoolJup, SPoolSnapshot, UnlistedSplLst};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_lst_list::SanctumLstList;
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use solana_sdk::pubkey::Pubkey;
+use test_utils::{jito_stake_pool, jitosol};
+
+use crate::{
+    fully_init_amm, jito_marinade_basic_amm, jito_marinade_basic_program_test, start_jito_marinade,
+    UpdatingSPoolJup,
+};
+
+#[tokio::test]
+async fn snapshot_round_trip() {
+    let (mut bc, s) = jito_marinade_basic_amm().await;
+    let json = serde_json::to_string(&s.to_snapshot().unwrap()).unwrap();
+    let snapshot: SPoolSnapshot = serde_json::from_str(&json).unwrap();
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+    let restored = SPoolJup::from_snapshot(snapshot, &sanctum_lst_list).unwrap();
+
+    assert_eq!(restored.program_id, s.program_id);
+    assert_eq!(restored.pool_state_account, s.pool_state_account);
+    assert_eq!(restored.lst_state_list_account, s.lst_state_list_account);
+    assert_eq!(
+        restored.fresh_lp_mint_supply().unwrap(),
+        s.fresh_lp_mint_supply().unwrap()
+    );
+    assert_eq!(restored.lst_data_list.len(), s.lst_data_list.len());
+    for (restored_ld, ld) in restored.lst_data_list.iter().zip(s.lst_data_list.iter()) {
+        assert_eq!(
+            restored_ld.as_ref().map(|ld| ld.reserves_balance),
+            ld.as_ref().map(|ld| ld.reserves_balance)
+        );
+    }
+    assert!(restored.pricing_prog.is_some());
+
+    // sol value calculators and pricing program still need their accounts
+    let restored = restored.update_with_banks(&mut bc).await;
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    assert_eq!(
+        restored.quote_full(&quote_params).unwrap().out_amount,
+        s.quote_full(&quote_params).unwrap().out_amount
+    );
+}
+
+#[tokio::test]
+async fn snapshot_round_trip_unlisted_spl_lst() {
+    let program_id = s_controller_lib::program::ID;
+    let mut bc = start_jito_marinade(jito_marinade_basic_program_test(Pubkey::new_unique())).await;
+    let SPoolInitKeys { lst_state_list, .. } = SPoolJup::init_keys(program_id);
+    let lst_state_list_acc = bc.get_account_unwrapped(lst_state_list).await;
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+    let lst_list_without_jitosol: Vec<_> = sanctum_lst_list
+        .into_iter()
+        .filter(|lst| lst.mint != jitosol::ID)
+        .collect();
+    let mut s = SPoolJup::from_lst_state_list_account(
+        program_id,
+        lst_state_list_acc,
+        &lst_list_without_jitosol,
+    )
+    .unwrap();
+    s.add_unlisted_spl_lst(
+        jitosol::ID,
+        UnlistedSplLst {
+            stake_pool_addr: jito_stake_pool::ID,
+            token_program: spl_token::ID,
+        },
+    );
+    let s = s.initial_update(&mut bc).await;
+
+    let json = serde_json::to_string(&s.to_snapshot().unwrap()).unwrap();
+    let snapshot: SPoolSnapshot = serde_json::from_str(&json).unwrap();
+    let restored = SPoolJup::from_snapshot(snapshot, &lst_list_without_jitosol).unwrap();
+    assert_eq!(restored.unlisted_spl_lsts, s.unlisted_spl_lsts);
+    restored.find_ready_lst(jitosol::ID).unwrap();
+
+    let restored = restored.update_with_banks(&mut bc).await;
+    let quote_params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    assert_eq!(
+        restored.quote_full(&quote_params).unwrap().out_amount,
+        s.quote_full(&quote_params).unwrap().out_amount
+    );
+}
+
+#[tokio::test]
+async fn snapshot_mismatched_calculator_rejected() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let mut snapshot = s.to_snapshot().unwrap();
+    snapshot.lsts[0].sol_value_calculator = Some(Pubkey::new_unique());
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+    assert!(SPoolJup::from_snapshot(snapshot.clone(), &sanctum_lst_list).is_err());
+
+    snapshot.lsts.pop();
+    assert!(SPoolJup::from_snapshot(snapshot, &sanctum_lst_list).is_err());
+}
+
+#[tokio::test]
+async fn reserves_snapshot_reproduces_quotes() {
+    let (mut bc, mut fetcher) = jito_marinade_basic_amm().await;
+    let mut quoter = fully_init_amm(&mut bc, s_controller_lib::program::ID).await;
+    fetcher
+        .lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap()
+        .reserves_balance = Some(5_000_000_000);
+    let lp_token_mint = fetcher.lp_token_mint().unwrap();
+    let all_quote_params =
+        [(jitosol::ID, msol::ID), (jitosol::ID, lp_token_mint)].map(|(input_mint, output_mint)| {
+            QuoteParams {
+                amount: 1_000_000_000,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactIn,
+            }
+        });
+    // add liquidity depends on the pool's total sol value
+    assert_ne!(
+        quoter.quote_full(&all_quote_params[1]).unwrap().out_amount,
+        fetcher.quote_full(&all_quote_params[1]).unwrap().out_amount
+    );
+
+    let snapshot = fetcher.reserves_snapshot();
+    assert_eq!(
+        snapshot,
+        vec![(jitosol::ID, 10_000_000_000), (msol::ID, 5_000_000_000)]
+    );
+    quoter.apply_reserves_snapshot(&snapshot).unwrap();
+
+    for quote_params in all_quote_params {
+        let Quote {
+            in_amount,
+            out_amount,
+            fee_amount,
+            ..
+        } = fetcher.quote_full(&quote_params).unwrap();
+        let quoted = quoter.quote_full(&quote_params).unwrap();
+        assert_eq!(quoted.in_amount, in_amount);
+        assert_eq!(quoted.out_amount, out_amount);
+        assert_eq!(quoted.fee_amount, fee_amount);
+    }
+}
+
+#[tokio::test]
+async fn reserves_snapshot_unknown_mint_rejected() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let before = s.reserves_snapshot();
+    assert!(s
+        .apply_reserves_snapshot(&[(msol::ID, 1), (Pubkey::new_unique(), 1)])
+        .is_err());
+    assert_eq!(s.reserves_snapshot(), before);
+}
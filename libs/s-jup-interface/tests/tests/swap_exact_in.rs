@@ -1,16 +1,20 @@
 use flat_fee_test_utils::MockFeeAccountArgs;
 use jupiter_amm_interface::{QuoteParams, SwapMode};
 use marinade_keys::msol;
+use s_controller_interface::SControllerError;
+use s_controller_lib::try_pool_state;
 use s_controller_test_utils::{
     jito_marinade_flat_fee_program_test, JitoMarinadeProgramTestArgs, MockProtocolFeeBps,
 };
+use s_jup_interface::{SPoolError, SPoolJup};
+use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc, WsolLstSolValCalc};
 use sanctum_solana_test_utils::ExtendedProgramTest;
 use sanctum_token_lib::MintWithTokenProgram;
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer};
 use test_utils::{jitosol, JITO_STAKE_POOL_LAST_UPDATE_EPOCH};
 
-use crate::{assert_quote_swap_eq, fully_init_amm, MiscProgramTest};
+use crate::{assert_quote_swap_eq, fully_init_amm, jito_marinade_basic_amm, MiscProgramTest};
 
 #[tokio::test]
 async fn swap_exact_in_jito_marinade_basic() {
@@ -92,3 +96,228 @@ async fn swap_exact_in_jito_marinade_basic() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn swap_exact_in_pricing_input_override() {
+    const AMT: u64 = 1_000_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = QuoteParams {
+        amount: AMT,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let actual_sol_value = s
+        .find_ready_lst(jitosol::ID)
+        .unwrap()
+        .1
+        .sol_val_calc
+        .lst_to_sol(AMT)
+        .unwrap()
+        .get_min();
+
+    let expected = s.quote_full(&params).unwrap();
+    let same = s
+        .quote_swap_exact_in_with_pricing_input_override(&params, actual_sol_value)
+        .unwrap();
+    assert_eq!(same.out_amount, expected.out_amount);
+
+    let halved = s
+        .quote_swap_exact_in_with_pricing_input_override(&params, actual_sol_value / 2)
+        .unwrap();
+    assert!(halved.out_amount < expected.out_amount);
+    assert_eq!(
+        halved.out_amount,
+        s.quote_swap_exact_in_with_pricing_input_override(&params, actual_sol_value / 2)
+            .unwrap()
+            .out_amount
+    );
+}
+
+#[tokio::test]
+async fn swap_exact_in_with_ret_sol_vals() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let total_sol_value = try_pool_state(&s.pool_state_data().unwrap())
+        .unwrap()
+        .total_sol_value;
+    let [jitosol_sol_value, msol_sol_value] =
+        [jitosol::ID, msol::ID].map(|mint| s.find_ready_lst(mint).unwrap().0.sol_value);
+    let expected = s.quote_full(&params).unwrap();
+
+    for (src_ret, dst_ret) in [(1, 2), (20_000_000_000, 5_000_000_000)] {
+        let (quote, pool_state) = s
+            .quote_swap_exact_in_with_ret_sol_vals(&params, src_ret, dst_ret)
+            .unwrap();
+        assert_eq!(
+            pool_state.total_sol_value,
+            total_sol_value - jitosol_sol_value - msol_sol_value + src_ret + dst_ret
+        );
+        assert_eq!(quote.out_amount, expected.out_amount);
+        assert_eq!(quote.fee_amount, expected.fee_amount);
+
+        let (again, again_pool_state) = s
+            .quote_swap_exact_in_with_ret_sol_vals(&params, src_ret, dst_ret)
+            .unwrap();
+        assert_eq!(again.out_amount, quote.out_amount);
+        assert_eq!(again_pool_state.total_sol_value, pool_state.total_sol_value);
+    }
+
+    assert!(s
+        .quote_swap_exact_in_with_ret_sol_vals(
+            &QuoteParams {
+                swap_mode: SwapMode::ExactOut,
+                ..params
+            },
+            1,
+            2
+        )
+        .is_err());
+}
+
+#[tokio::test]
+async fn cached_reserves_sol_values_match_uncached() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    assert!(s
+        .lst_data_list
+        .iter()
+        .flatten()
+        .all(|ld| ld.reserves_sol_value.is_some()));
+    let mut uncached = s.clone();
+    uncached
+        .lst_data_list
+        .iter_mut()
+        .flatten()
+        .for_each(|ld| ld.reserves_sol_value = None);
+
+    for amount in (1..=1_000).map(|i| i * 1_000_000) {
+        for (input_mint, output_mint) in [(jitosol::ID, msol::ID), (msol::ID, jitosol::ID)] {
+            let params = QuoteParams {
+                amount,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactIn,
+            };
+            let cached_quote = s.quote_full(&params).unwrap();
+            let uncached_quote = uncached.quote_full(&params).unwrap();
+            assert_eq!(cached_quote.out_amount, uncached_quote.out_amount);
+            assert_eq!(cached_quote.fee_amount, uncached_quote.fee_amount);
+            assert_eq!(
+                cached_quote.not_enough_liquidity,
+                uncached_quote.not_enough_liquidity
+            );
+        }
+    }
+    assert_eq!(
+        s.sol_value_sync_drift().unwrap(),
+        uncached.sol_value_sync_drift().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn detailed_fee_breakdown_sums_to_quote_fee() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let (quote, fees) = s.quote_swap_exact_in_detailed(&params).unwrap();
+    let expected_quote = s.quote_full(&params).unwrap();
+    assert_eq!(quote.out_amount, expected_quote.out_amount);
+    assert_eq!(quote.fee_amount, expected_quote.fee_amount);
+
+    assert_eq!(fees, s.quote_swap_exact_in_fees(&params).unwrap());
+    assert_eq!(fees.protocol_fee + fees.lp_fee, quote.fee_amount);
+    // 1000 bps trading protocol fee
+    assert!(fees.protocol_fee > 0);
+    assert!(fees.protocol_fee <= quote.fee_amount / 10 + 1);
+}
+
+#[tokio::test]
+async fn trace_matches_quote() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let expected = s.quote_full(&params).unwrap();
+    let (_quote, fees) = s.quote_swap_exact_in_detailed(&params).unwrap();
+    let trace = s.quote_swap_exact_in_trace(&params).unwrap();
+
+    assert_eq!(trace.quote.in_amount, expected.in_amount);
+    assert_eq!(trace.quote.out_amount, expected.out_amount);
+    assert_eq!(trace.quote.fee_amount, expected.fee_amount);
+    assert_eq!(trace.dst_lst_out, expected.out_amount);
+    assert_eq!(trace.to_protocol_fees_lst_amount, fees.protocol_fee);
+    assert_eq!(
+        trace.quote.fee_amount - trace.to_protocol_fees_lst_amount,
+        fees.lp_fee
+    );
+    assert!(trace.in_sol_value > trace.out_sol_value);
+    assert_eq!(
+        trace.in_sol_value,
+        s.sol_val_calc_for(jitosol::ID)
+            .unwrap()
+            .lst_to_sol(params.amount)
+            .unwrap()
+            .get_min()
+    );
+    assert_eq!(trace.synced_input_mint, jitosol::ID);
+    assert_eq!(trace.synced_output_mint, msol::ID);
+    assert!(trace.synced_total_sol_value > 0);
+}
+
+/// Values mSOL 1:1 like WSOL, with `reserves` as its reserves balance
+fn set_msol_wsol_calc(s: &mut SPoolJup, reserves: u64) {
+    let msol_data = s
+        .lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == msol::ID)
+        .unwrap();
+    msol_data.sol_val_calc = KnownLstSolValCalc::Wsol(WsolLstSolValCalc);
+    msol_data.reserves_balance = Some(reserves);
+    msol_data.reserves_sol_value = None;
+}
+
+#[tokio::test]
+async fn wsol_output_near_u64_max_errors_cleanly() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let quote_params = |amount| QuoteParams {
+        amount,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+
+    // reserves worth close to u64::MAX overflow the pool's total sol value on sync
+    set_msol_wsol_calc(&mut s, u64::MAX - 1);
+    assert!(matches!(
+        s.quote_full(&quote_params(1_000_000_000)),
+        Err(SPoolError::SController(SControllerError::MathError))
+    ));
+
+    // largest output that still fits: out amount + protocol fees is bounded by the input's
+    // sol value for 1:1 output so it does not wrap
+    set_msol_wsol_calc(&mut s, u64::MAX / 2);
+    let amount = u64::MAX / 8;
+    let (_, jitosol_data) = s.find_ready_lst(jitosol::ID).unwrap();
+    let in_sol_value = jitosol_data
+        .sol_val_calc
+        .lst_to_sol(amount)
+        .unwrap()
+        .get_min();
+    let quote = s.quote_full(&quote_params(amount)).unwrap();
+    assert!(quote.out_amount <= in_sol_value);
+    assert!(quote.out_amount.checked_add(quote.fee_amount).is_some());
+}
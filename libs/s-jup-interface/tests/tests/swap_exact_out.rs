@@ -1,16 +1,24 @@
 use flat_fee_test_utils::MockFeeAccountArgs;
-use jupiter_amm_interface::{QuoteParams, SwapMode};
+use jupiter_amm_interface::{QuoteParams, SwapMode, SwapParams};
 use marinade_keys::msol;
+use s_controller_interface::SwapExactOutIxData;
 use s_controller_test_utils::{
     jito_marinade_flat_fee_program_test, JitoMarinadeProgramTestArgs, MockProtocolFeeBps,
 };
-use sanctum_solana_test_utils::ExtendedProgramTest;
-use sanctum_token_lib::MintWithTokenProgram;
+use s_jup_interface::swap_and_account_metas_to_ix;
+use sanctum_associated_token_lib::FindAtaAddressArgs;
+use sanctum_solana_test_utils::{ExtendedBanksClient, ExtendedProgramTest};
+use sanctum_token_lib::{token_account_balance, MintWithTokenProgram};
 use solana_program_test::ProgramTestContext;
-use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use solana_sdk::{
+    clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+};
 use test_utils::{jitosol, JITO_STAKE_POOL_LAST_UPDATE_EPOCH};
 
-use crate::{assert_quote_swap_eq, fully_init_amm, MiscProgramTest};
+use crate::{
+    assert_quote_swap_eq, fully_init_amm, jito_marinade_basic_amm,
+    jito_marinade_basic_program_test, start_jito_marinade, MiscProgramTest,
+};
 
 #[tokio::test]
 async fn swap_exact_out_jito_marinade_basic() {
@@ -92,3 +100,160 @@ async fn swap_exact_out_jito_marinade_basic() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn exact_in_exact_out_round_trip() {
+    // rounding tolerance in LST atomics
+    const TOLERANCE: u64 = 2;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    for (input_mint, output_mint) in [(jitosol::ID, msol::ID), (msol::ID, jitosol::ID)] {
+        for amount in [1_000_000, 1_000_000_000, 3_000_000_000] {
+            let exact_in = s
+                .quote_full(&QuoteParams {
+                    amount,
+                    input_mint,
+                    output_mint,
+                    swap_mode: SwapMode::ExactIn,
+                })
+                .unwrap();
+            let exact_out = s
+                .quote_full(&QuoteParams {
+                    amount: exact_in.out_amount,
+                    input_mint,
+                    output_mint,
+                    swap_mode: SwapMode::ExactOut,
+                })
+                .unwrap();
+            assert_eq!(exact_out.out_amount, exact_in.out_amount);
+            assert!(exact_out.in_amount.abs_diff(amount) <= TOLERANCE);
+
+            // exact out rounds input up, so feeding it forward
+            // must give back at least about the same output
+            let fed_forward = s
+                .quote_full(&QuoteParams {
+                    amount: exact_out.in_amount,
+                    input_mint,
+                    output_mint,
+                    swap_mode: SwapMode::ExactIn,
+                })
+                .unwrap();
+            assert!(fed_forward.out_amount + TOLERANCE >= exact_in.out_amount);
+        }
+    }
+}
+
+#[tokio::test]
+async fn safe_max_amount_in_does_not_revert() {
+    const WALLET_JITOSOL: u64 = 10_000_000_000;
+
+    let wallet = Keypair::new();
+    let pt = jito_marinade_basic_program_test(Pubkey::new_unique())
+        .add_system_account(wallet.pubkey(), 1_000_000_000)
+        .add_ata(
+            wallet.pubkey(),
+            MintWithTokenProgram {
+                pubkey: jitosol::ID,
+                token_program: spl_token::ID,
+            },
+            WALLET_JITOSOL,
+        )
+        .add_ata(
+            wallet.pubkey(),
+            MintWithTokenProgram {
+                pubkey: msol::ID,
+                token_program: spl_token::ID,
+            },
+            0,
+        );
+    let mut bc = start_jito_marinade(pt).await;
+    let [src_lst_acc, dst_lst_acc] = [jitosol::ID, msol::ID].map(|mint| {
+        FindAtaAddressArgs {
+            wallet: wallet.pubkey(),
+            mint,
+            token_program: spl_token::ID,
+        }
+        .find_ata_address()
+        .0
+    });
+
+    // deterministic pseudorandom amounts in [1_000, 500_000_000)
+    let mut seed: u64 = 0x5EED;
+    for _ in 0..8 {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let amount = 1_000 + (seed >> 33) % 499_999_000;
+
+        let s = fully_init_amm(&mut bc, s_controller_lib::program::ID).await;
+        let quote_params = QuoteParams {
+            amount,
+            input_mint: jitosol::ID,
+            output_mint: msol::ID,
+            swap_mode: SwapMode::ExactOut,
+        };
+        let max_amount_in = s.safe_max_amount_in(&quote_params).unwrap();
+        assert!(max_amount_in > s.quote_full(&quote_params).unwrap().in_amount);
+        let ix = s
+            .swap_ix_with_other_amount_threshold(
+                &SwapParams {
+                    in_amount: 0,
+                    out_amount: amount,
+                    source_mint: jitosol::ID,
+                    destination_mint: msol::ID,
+                    source_token_account: src_lst_acc,
+                    destination_token_account: dst_lst_acc,
+                    token_transfer_authority: wallet.pubkey(),
+                    open_order_address: None,
+                    quote_mint_to_referrer: None,
+                    jupiter_program_id: &Pubkey::default(),
+                    missing_dynamic_accounts_as_default: false,
+                },
+                SwapMode::ExactOut,
+                max_amount_in,
+            )
+            .unwrap();
+        let src_before =
+            token_account_balance(bc.get_account_unwrapped(src_lst_acc).await).unwrap();
+        let mut tx = Transaction::new_with_payer(&[ix], Some(&wallet.pubkey()));
+        tx.sign(&[&wallet], bc.get_latest_blockhash().await.unwrap());
+        bc.process_transaction(tx).await.unwrap();
+        let src_after = token_account_balance(bc.get_account_unwrapped(src_lst_acc).await).unwrap();
+        assert!(src_before - src_after <= max_amount_in);
+    }
+}
+
+#[tokio::test]
+async fn exact_out_ix_args_take_threshold_from_swap_params() {
+    const MAX_AMOUNT_IN: u64 = 1_000_000_000;
+    const AMOUNT: u64 = 900_000_000;
+
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let jupiter_program_id = Pubkey::default();
+    let swap_params = SwapParams {
+        in_amount: MAX_AMOUNT_IN,
+        out_amount: AMOUNT,
+        source_mint: jitosol::ID,
+        destination_mint: msol::ID,
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        token_transfer_authority: Pubkey::new_unique(),
+        open_order_address: None,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &jupiter_program_id,
+        missing_dynamic_accounts_as_default: false,
+    };
+    let (metas, args) = s
+        .swap_exact_out_account_metas_and_args(&swap_params)
+        .unwrap();
+    assert_eq!(args.max_amount_in, MAX_AMOUNT_IN);
+    assert_eq!(args.amount, AMOUNT);
+
+    let ix = swap_and_account_metas_to_ix(
+        s.program_id,
+        metas,
+        SwapExactOutIxData(args).try_to_vec().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(ix, s.swap_ix(&swap_params, SwapMode::ExactOut).unwrap());
+}
@@ -0,0 +1,64 @@
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::SyntheticOutputLst;
+use s_sol_val_calc_prog_aggregate::{KnownLstSolValCalc, LstSolValCalc, WsolLstSolValCalc};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+const AMOUNT: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn synthetic_output_with_pool_calc_matches_regular_quote() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let expected = s
+        .quote_full(&QuoteParams {
+            amount: AMOUNT,
+            input_mint: jitosol::ID,
+            output_mint: msol::ID,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    let (_, msol_data) = s.find_ready_lst(msol::ID).unwrap();
+    let quote = s
+        .quote_swap_exact_in_synthetic(
+            jitosol::ID,
+            AMOUNT,
+            SyntheticOutputLst {
+                sol_val_calc: &msol_data.sol_val_calc,
+                pricing_output_mint: msol::ID,
+            },
+        )
+        .unwrap();
+    assert_eq!(quote.out_amount, expected.out_amount);
+    assert_eq!(quote.fee_amount, expected.fee_amount);
+    assert_eq!(quote.fee_mint, msol::ID);
+}
+
+#[tokio::test]
+async fn synthetic_output_wsol() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let msol_quote = s
+        .quote_full(&QuoteParams {
+            amount: AMOUNT,
+            input_mint: jitosol::ID,
+            output_mint: msol::ID,
+            swap_mode: SwapMode::ExactIn,
+        })
+        .unwrap();
+    let wsol_calc = KnownLstSolValCalc::Wsol(WsolLstSolValCalc);
+    let quote = s
+        .quote_swap_exact_in_synthetic(
+            jitosol::ID,
+            AMOUNT,
+            SyntheticOutputLst {
+                sol_val_calc: &wsol_calc,
+                pricing_output_mint: msol::ID,
+            },
+        )
+        .unwrap();
+    // same sol value out, but each mSOL is worth more than 1 SOL
+    assert!(quote.out_amount > msol_quote.out_amount);
+    assert_eq!(quote.fee_mint, wsol_calc.lst_mint());
+    assert!(!quote.not_enough_liquidity);
+}
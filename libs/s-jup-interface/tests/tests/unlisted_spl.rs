@@ -0,0 +1,64 @@
+use jupiter_amm_interface::{Amm, Quote, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use s_jup_interface::{SPoolError, SPoolInitKeys, SPoolJup, UnlistedSplLst};
+use sanctum_lst_list::SanctumLstList;
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use solana_sdk::pubkey::Pubkey;
+use test_utils::{jito_stake_pool, jitosol};
+
+use crate::{
+    fully_init_amm, jito_marinade_basic_program_test, start_jito_marinade, UpdatingSPoolJup,
+};
+
+#[tokio::test]
+async fn unlisted_spl_lst_quotes_same_as_listed() {
+    const JITOSOL_TO_MSOL: QuoteParams = QuoteParams {
+        amount: 1_000_000_000,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+
+    let program_id = s_controller_lib::program::ID;
+    let mut bc = start_jito_marinade(jito_marinade_basic_program_test(Pubkey::new_unique())).await;
+    let SPoolInitKeys { lst_state_list, .. } = SPoolJup::init_keys(program_id);
+    let lst_state_list_acc = bc.get_account_unwrapped(lst_state_list).await;
+    let SanctumLstList { sanctum_lst_list } = SanctumLstList::load();
+    let lst_list_without_jitosol: Vec<_> = sanctum_lst_list
+        .into_iter()
+        .filter(|lst| lst.mint != jitosol::ID)
+        .collect();
+    let mut unlisted = SPoolJup::from_lst_state_list_account(
+        program_id,
+        lst_state_list_acc,
+        &lst_list_without_jitosol,
+    )
+    .unwrap();
+    assert!(matches!(
+        unlisted.find_ready_lst(jitosol::ID),
+        Err(SPoolError::LstNotSupported(_))
+    ));
+
+    unlisted.add_unlisted_spl_lst(
+        jitosol::ID,
+        UnlistedSplLst {
+            stake_pool_addr: jito_stake_pool::ID,
+            token_program: spl_token::ID,
+        },
+    );
+    let unlisted = unlisted.initial_update(&mut bc).await;
+    let listed = fully_init_amm(&mut bc, program_id).await;
+
+    let Quote {
+        out_amount: unlisted_out,
+        fee_amount: unlisted_fee,
+        ..
+    } = unlisted.quote(&JITOSOL_TO_MSOL).unwrap();
+    let Quote {
+        out_amount: listed_out,
+        fee_amount: listed_fee,
+        ..
+    } = listed.quote(&JITOSOL_TO_MSOL).unwrap();
+    assert_eq!(unlisted_out, listed_out);
+    assert_eq!(unlisted_fee, listed_fee);
+}
@@ -0,0 +1,104 @@
+use marinade_keys::msol;
+use s_controller_lib::find_pool_state_address;
+use s_jup_interface::{LstData, SPoolJup};
+use s_pricing_prog_aggregate::{KnownPricingProg, MutablePricingProg};
+use s_sol_val_calc_prog_aggregate::{
+    KnownLstSolValCalc, MarinadeLstSolValCalc, SplLstSolValCalc, SplLstSolValCalcInitKeys,
+};
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use solana_sdk::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+fn spl_lst_data(lst_mint: Pubkey, stake_pool_addr: Pubkey) -> Option<LstData> {
+    Some(LstData {
+        sol_val_calc: KnownLstSolValCalc::Spl(SplLstSolValCalc::from_keys(
+            SplLstSolValCalcInitKeys {
+                lst_mint,
+                stake_pool_addr,
+            },
+        )),
+        reserves_balance: None,
+        token_program: spl_token::ID,
+        reserves_sol_value: None,
+        last_updated_slot: None,
+    })
+}
+
+#[test]
+fn detect_calc_account_collisions_shared_stake_pool() {
+    let [mint_a, mint_b, mint_c, shared_pool, other_pool] = [(); 5].map(|_| Pubkey::new_unique());
+    let s = SPoolJup {
+        lst_data_list: vec![
+            spl_lst_data(mint_a, shared_pool),
+            spl_lst_data(mint_b, other_pool),
+            None,
+            spl_lst_data(mint_c, shared_pool),
+            Some(LstData {
+                sol_val_calc: KnownLstSolValCalc::Marinade(MarinadeLstSolValCalc::default()),
+                reserves_balance: None,
+                token_program: spl_token::ID,
+                reserves_sol_value: None,
+                last_updated_slot: None,
+            }),
+        ],
+        ..Default::default()
+    };
+    assert_eq!(s.detect_calc_account_collisions(), vec![(mint_a, mint_c)]);
+}
+
+#[test]
+fn validate_addresses_default_ok() {
+    SPoolJup::default().validate_addresses().unwrap();
+}
+
+#[test]
+fn validate_addresses_mismatched_pool_state() {
+    let other_program = Pubkey::new_unique();
+    let s = SPoolJup {
+        pool_state_addr: find_pool_state_address(other_program).0,
+        ..Default::default()
+    };
+    assert!(s.validate_addresses().is_err());
+}
+
+#[tokio::test]
+async fn pricing_mints_current() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    s.assert_pricing_mints_current().unwrap();
+}
+
+#[tokio::test]
+async fn pricing_mints_drifted() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let stale = Pubkey::new_unique();
+    s.pricing_prog = Some(
+        KnownPricingProg::try_new(flat_fee_interface::ID, [jitosol::ID, stale].into_iter())
+            .unwrap(),
+    );
+    let err = s.assert_pricing_mints_current().unwrap_err().to_string();
+    assert!(err.contains(&msol::ID.to_string()));
+    assert!(err.contains(&stale.to_string()));
+}
+
+#[tokio::test]
+async fn lp_mint_authority_is_pool_state_pda() {
+    let (mut bc, s) = jito_marinade_basic_amm().await;
+    let authority = s.lp_mint_authority().unwrap();
+    assert_eq!(authority, find_pool_state_address(s.program_id).0);
+    assert_eq!(authority, s_controller_lib::program::POOL_STATE_ID);
+
+    let lp_mint_acc = bc.get_account_unwrapped(s.lp_token_mint().unwrap()).await;
+    let lp_mint = spl_token::state::Mint::unpack(&lp_mint_acc.data).unwrap();
+    assert_eq!(lp_mint.mint_authority, COption::Some(authority));
+}
+
+#[test]
+fn lp_mint_authority_mismatched_pool_state() {
+    let s = SPoolJup {
+        pool_state_addr: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    assert!(s.lp_mint_authority().is_err());
+}
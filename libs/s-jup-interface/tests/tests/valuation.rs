@@ -0,0 +1,239 @@
+use std::{collections::HashMap, sync::Arc};
+
+use jupiter_amm_interface::{Amm, QuoteParams, SwapMode};
+use marinade_keys::msol;
+use rust_decimal::Decimal;
+use s_controller_interface::{
+    SyncSolValueIxArgs, SyncSolValueIxData, SYNC_SOL_VALUE_IX_ACCOUNTS_LEN, SYNC_SOL_VALUE_IX_DISCM,
+};
+use s_controller_lib::{
+    try_lst_state_list, try_lst_state_list_mut, try_pool_state, try_pool_state_mut,
+};
+use s_sol_val_calc_prog_aggregate::LstSolValCalc;
+use sanctum_solana_test_utils::ExtendedBanksClient;
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey};
+use test_utils::jitosol;
+
+use crate::{jito_marinade_basic_amm, JITO_MARINADE_BASIC_LP_TOKEN_SUPPLY};
+
+#[tokio::test]
+async fn tvl_in_usd_linear_in_price() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let tvl_at_one = s.tvl_in_usd(Decimal::ONE).unwrap();
+    assert!(tvl_at_one > Decimal::ZERO);
+    for price in [Decimal::ZERO, Decimal::TWO, Decimal::new(14_523, 2)] {
+        assert_eq!(s.tvl_in_usd(price).unwrap(), tvl_at_one * price);
+    }
+}
+
+#[tokio::test]
+async fn sol_value_sync_drift_stale_pool_state() {
+    const STALE_BY: u64 = 1_000_000;
+
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let drift = s.sol_value_sync_drift().unwrap();
+
+    let pool_state_acc = Arc::make_mut(s.pool_state_account.as_mut().unwrap());
+    try_pool_state_mut(&mut pool_state_acc.data)
+        .unwrap()
+        .total_sol_value -= STALE_BY;
+    assert_eq!(
+        s.sol_value_sync_drift().unwrap(),
+        drift + i128::from(STALE_BY)
+    );
+    assert_ne!(s.sol_value_sync_drift().unwrap(), 0);
+}
+
+#[tokio::test]
+async fn drift_correction_ixs_skip_synced_lsts() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    // both LSTs' sol values are recorded as 1:1 with their reserves
+    let ixs = s.drift_correction_ixs().unwrap();
+    assert_eq!(ixs.len(), 2);
+    for ix in ixs.iter() {
+        assert_eq!(ix.program_id, s.program_id);
+        assert_eq!(ix.data[0], SYNC_SOL_VALUE_IX_DISCM);
+    }
+
+    let jitosol_synced_sol_value = {
+        let ld = s
+            .lst_data_list
+            .iter()
+            .flatten()
+            .find(|ld| ld.sol_val_calc.lst_mint() == jitosol::ID)
+            .unwrap();
+        ld.sol_val_calc
+            .lst_to_sol(ld.reserves_balance.unwrap())
+            .unwrap()
+            .get_min()
+    };
+    try_lst_state_list_mut(&mut Arc::make_mut(&mut s.lst_state_list_account).data)
+        .unwrap()
+        .iter_mut()
+        .find(|ls| ls.mint == jitosol::ID)
+        .unwrap()
+        .sol_value = jitosol_synced_sol_value;
+    let ixs = s.drift_correction_ixs().unwrap();
+    assert_eq!(ixs.len(), 1);
+    assert!(ixs[0]
+        .accounts
+        .iter()
+        .all(|meta| meta.pubkey != jitosol::ID));
+}
+
+#[tokio::test]
+async fn lst_sol_values_sum_to_synced_total() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let reserves = s.lst_reserves();
+    assert_eq!(reserves.len(), 2);
+    assert!(reserves.iter().all(|(_mint, b)| *b == Some(10_000_000_000)));
+
+    let sol_values = s.lst_sol_values().unwrap();
+    let sum: u64 = sol_values.iter().map(|(_mint, v)| v.unwrap()).sum();
+    let total_sol_value = try_pool_state(&s.pool_state_data().unwrap())
+        .unwrap()
+        .total_sol_value;
+    let drift = s.sol_value_sync_drift().unwrap();
+    assert_eq!(i128::from(sum), i128::from(total_sol_value) + drift);
+    // LSTs are worth at least as much SOL as their recorded 1:1 sol values
+    assert!(sum >= total_sol_value);
+
+    s.lst_data_list
+        .iter_mut()
+        .flatten()
+        .find(|ld| ld.sol_val_calc.lst_mint() == jitosol::ID)
+        .unwrap()
+        .reserves_balance = None;
+    assert!(s
+        .lst_sol_values()
+        .unwrap()
+        .iter()
+        .any(|(mint, v)| *mint == jitosol::ID && v.is_none()));
+}
+
+#[tokio::test]
+async fn total_sol_value_and_lp_token_price() {
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let lst_sol_values_sum: u64 = s
+        .lst_sol_values()
+        .unwrap()
+        .iter()
+        .map(|(_mint, v)| v.unwrap())
+        .sum();
+    let total_sol_value = s.total_sol_value().unwrap();
+    assert_eq!(total_sol_value, lst_sol_values_sum);
+    // 10 jitoSOL + 10 mSOL, both worth more than 1 SOL each
+    assert!(total_sol_value > 20_000_000_000);
+
+    let price = s.lp_token_price_sol().unwrap();
+    assert_eq!(
+        price,
+        Decimal::from(total_sol_value) / Decimal::from(JITO_MARINADE_BASIC_LP_TOKEN_SUPPLY)
+    );
+    assert!(price > Decimal::ONE);
+
+    s.lp_mint_supply = Some(0);
+    assert!(s.lp_token_price_sol().is_err());
+}
+
+#[tokio::test]
+async fn fee_bearing_swap_grows_nav() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    for (input_mint, output_mint) in [(jitosol::ID, msol::ID), (msol::ID, jitosol::ID)] {
+        let delta = s
+            .nav_delta_from_swap(&QuoteParams {
+                amount: 1_000_000_000,
+                input_mint,
+                output_mint,
+                swap_mode: SwapMode::ExactIn,
+            })
+            .unwrap();
+        assert!(delta >= Decimal::ZERO);
+    }
+}
+
+#[tokio::test]
+async fn min_nonzero_sol_value_amount_is_tight() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    for mint in [jitosol::ID, msol::ID] {
+        let min = s.min_nonzero_sol_value_amount(mint).unwrap();
+        let (_, lst_data) = s.find_ready_lst(mint).unwrap();
+        let sol_value = |amount| lst_data.sol_val_calc.lst_to_sol(amount).unwrap().get_min();
+        for below in [0, min / 2, min - 1] {
+            assert_eq!(sol_value(below), 0);
+        }
+        for at_or_above in [min, min + 1, min * 2] {
+            assert!(sol_value(at_or_above) > 0);
+        }
+    }
+}
+
+#[tokio::test]
+async fn sol_val_calc_for_converts_outside_pool() {
+    const AMT: u64 = 1_000_000_000;
+
+    let (_bc, mut s) = jito_marinade_basic_amm().await;
+    let calc = s.sol_val_calc_for(jitosol::ID).unwrap();
+    let (_, lst_data) = s.find_ready_lst(jitosol::ID).unwrap();
+    assert_eq!(
+        calc.lst_to_sol(AMT).unwrap().get_min(),
+        lst_data.sol_val_calc.lst_to_sol(AMT).unwrap().get_min()
+    );
+    assert!(calc.lst_to_sol(AMT).unwrap().get_min() > 0);
+
+    *s.lst_data_list
+        .iter_mut()
+        .find(|ld| matches!(ld, Some(ld) if ld.sol_val_calc.lst_mint() == msol::ID))
+        .unwrap() = None;
+    assert!(s.sol_val_calc_for(msol::ID).is_none());
+}
+
+#[tokio::test]
+async fn sync_sol_value_ix_by_mint_resolves_index_and_calc_accounts() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let lst_state_list = try_lst_state_list(&s.lst_state_list_account.data).unwrap();
+    for (expected_index, lst_state) in lst_state_list.iter().enumerate() {
+        let ix = s.sync_sol_value_ix_by_mint(lst_state.mint).unwrap();
+        let SyncSolValueIxData(SyncSolValueIxArgs { lst_index }) =
+            SyncSolValueIxData::deserialize(&ix.data).unwrap();
+        assert_eq!(usize::try_from(lst_index).unwrap(), expected_index);
+
+        let sol_val_calc = s.sol_val_calc_for(lst_state.mint).unwrap();
+        // calculator program replaces the LST mint prefix of the calculator accounts
+        let calc_accounts = &ix.accounts[SYNC_SOL_VALUE_IX_ACCOUNTS_LEN..];
+        assert_eq!(calc_accounts.len(), sol_val_calc.ix_accounts().len());
+        assert_eq!(
+            calc_accounts[0].pubkey,
+            sol_val_calc.sol_value_calculator_program_id()
+        );
+    }
+    assert!(s.sync_sol_value_ix_by_mint(Pubkey::new_unique()).is_err());
+}
+
+#[tokio::test]
+async fn potential_sol_value_shift_from_changed_reserves() {
+    let (mut bc, mut s) = jito_marinade_basic_amm().await;
+    let no_change: HashMap<Pubkey, Account> = HashMap::new();
+    assert_eq!(s.potential_sol_value_shift(&no_change).unwrap(), 0);
+
+    let (lst_state, lst_data) = s.find_ready_lst(msol::ID).unwrap();
+    let msol_reserves = s.pool_reserves_account(&lst_state, lst_data).unwrap();
+    let mut reserves_acc = bc.get_account_unwrapped(msol_reserves).await;
+    let mut token_acc = spl_token::state::Account::unpack(&reserves_acc.data).unwrap();
+    let old_balance = token_acc.amount;
+    token_acc.amount += 1_000_000_000;
+    spl_token::state::Account::pack(token_acc, &mut reserves_acc.data).unwrap();
+    let fresh = HashMap::from([(msol_reserves, reserves_acc)]);
+
+    let calc = s.sol_val_calc_for(msol::ID).unwrap();
+    let expected = i128::from(calc.lst_to_sol(token_acc.amount).unwrap().get_min())
+        - i128::from(calc.lst_to_sol(old_balance).unwrap().get_min());
+    let shift = s.potential_sol_value_shift(&fresh).unwrap();
+    assert_eq!(shift, expected);
+    assert!(shift > 0);
+
+    let total_before = s.total_sol_value().unwrap();
+    s.update(&fresh).unwrap();
+    let total_after = s.total_sol_value().unwrap();
+    assert_eq!(i128::from(total_after) - i128::from(total_before), shift);
+}
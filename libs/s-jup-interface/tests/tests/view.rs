@@ -0,0 +1,36 @@
+use std::thread;
+
+use jupiter_amm_interface::{QuoteParams, SwapMode};
+use marinade_keys::msol;
+use test_utils::jitosol;
+
+use crate::jito_marinade_basic_amm;
+
+#[tokio::test]
+async fn quote_view_from_multiple_threads() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let view = s.to_view();
+    let amounts = [1_000_000, 100_000_000, 1_000_000_000, 5_000_000_000];
+
+    let quote_params = |amount| QuoteParams {
+        amount,
+        input_mint: jitosol::ID,
+        output_mint: msol::ID,
+        swap_mode: SwapMode::ExactIn,
+    };
+    let expected: Vec<u64> = amounts
+        .iter()
+        .map(|amount| s.quote_full(&quote_params(*amount)).unwrap().out_amount)
+        .collect();
+    let from_threads: Vec<u64> = thread::scope(|scope| {
+        let handles: Vec<_> = amounts
+            .iter()
+            .map(|amount| {
+                let view = view.clone();
+                scope.spawn(move || view.quote(&quote_params(*amount)).unwrap().out_amount)
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert_eq!(from_threads, expected);
+}
@@ -0,0 +1,159 @@
+use flat_fee_test_utils::MockFeeAccountArgs;
+use jupiter_amm_interface::{SwapMode, SwapParams};
+use marinade_keys::msol;
+use s_controller_test_utils::{
+    jito_wsol_flat_fee_program_test, JitoWsolProgramTestArgs, MockProtocolFeeBps,
+};
+use s_jup_interface::{SPoolJup, WsolWrappedSwapIxs};
+use solana_sdk::{pubkey::Pubkey, system_program};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::native_mint;
+use test_utils::jitosol;
+
+use crate::{fully_init_amm, jito_marinade_basic_amm, start_jito_marinade, MiscProgramTest};
+
+// unused, the instructions are for the pool program directly
+const JUPITER_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0; 32]);
+
+async fn jito_wsol_amm() -> SPoolJup {
+    let pt = jito_wsol_flat_fee_program_test(
+        JitoWsolProgramTestArgs {
+            jitosol_sol_value: 10_000_000_000,
+            wsol_sol_value: 10_000_000_000,
+            jitosol_reserves: 10_000_000_000,
+            wsol_reserves: 10_000_000_000,
+            ..Default::default()
+        }
+        .with_lp_token_mint(Pubkey::new_unique()),
+        flat_fee_interface::ProgramState {
+            manager: Pubkey::default(),
+            lp_withdrawal_fee_bps: 0,
+        },
+        [jitosol::ID, native_mint::ID].map(|lst_mint| MockFeeAccountArgs {
+            input_fee_bps: 4,
+            output_fee_bps: 4,
+            lst_mint,
+        }),
+        MockProtocolFeeBps {
+            trading: 1000,
+            lp: 1000,
+        },
+    )
+    .add_s_program();
+    let mut bc = start_jito_marinade(pt).await;
+    fully_init_amm(&mut bc, s_controller_lib::program::ID).await
+}
+
+fn swap_params(
+    source_mint: Pubkey,
+    destination_mint: Pubkey,
+    owner: Pubkey,
+    jupiter_program_id: &Pubkey,
+) -> SwapParams {
+    SwapParams {
+        in_amount: 1_000_000_000,
+        out_amount: 0,
+        source_mint,
+        destination_mint,
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        token_transfer_authority: owner,
+        open_order_address: None,
+        quote_mint_to_referrer: None,
+        jupiter_program_id,
+        missing_dynamic_accounts_as_default: false,
+    }
+}
+
+#[tokio::test]
+async fn wrap_sol_in_creates_and_closes_ata() {
+    let s = jito_wsol_amm().await;
+    let owner = Pubkey::new_unique();
+    let wsol_ata = get_associated_token_address(&owner, &native_mint::ID);
+    let params = swap_params(native_mint::ID, jitosol::ID, owner, &JUPITER_PROGRAM_ID);
+
+    let WsolWrappedSwapIxs {
+        prepend,
+        swap,
+        append,
+    } = s
+        .swap_ixs_with_wsol_wrap(&params, SwapMode::ExactIn, false)
+        .unwrap();
+    // create ATA, transfer, sync native
+    assert_eq!(prepend.len(), 3);
+    assert_eq!(prepend[0].program_id, spl_associated_token_account::ID);
+    assert_eq!(prepend[1].program_id, system_program::ID);
+    assert_eq!(prepend[2].program_id, spl_token::ID);
+    assert!(prepend[2].accounts.iter().any(|m| m.pubkey == wsol_ata));
+    assert_eq!(append.len(), 1);
+    assert_eq!(append[0].program_id, spl_token::ID);
+    assert_eq!(append[0].accounts[0].pubkey, wsol_ata);
+
+    let expected_swap = s
+        .swap_ix(
+            &SwapParams {
+                source_token_account: wsol_ata,
+                ..params
+            },
+            SwapMode::ExactIn,
+        )
+        .unwrap();
+    assert_eq!(swap, expected_swap);
+}
+
+#[tokio::test]
+async fn wrap_sol_in_existing_ata_skips_create_and_close() {
+    let s = jito_wsol_amm().await;
+    let params = swap_params(
+        native_mint::ID,
+        jitosol::ID,
+        Pubkey::new_unique(),
+        &JUPITER_PROGRAM_ID,
+    );
+
+    let WsolWrappedSwapIxs {
+        prepend, append, ..
+    } = s
+        .swap_ixs_with_wsol_wrap(&params, SwapMode::ExactIn, true)
+        .unwrap();
+    // transfer, sync native
+    assert_eq!(prepend.len(), 2);
+    assert!(prepend
+        .iter()
+        .all(|ix| ix.program_id != spl_associated_token_account::ID));
+    assert!(append.is_empty());
+}
+
+#[tokio::test]
+async fn unwrap_sol_out_closes_created_ata() {
+    let s = jito_wsol_amm().await;
+    let owner = Pubkey::new_unique();
+    let params = swap_params(jitosol::ID, native_mint::ID, owner, &JUPITER_PROGRAM_ID);
+
+    let ixs = s
+        .swap_ixs_with_wsol_wrap(&params, SwapMode::ExactIn, false)
+        .unwrap();
+    // nothing to wrap, only create the ATA to receive the output in
+    assert_eq!(ixs.prepend.len(), 1);
+    assert_eq!(ixs.prepend[0].program_id, spl_associated_token_account::ID);
+    assert_eq!(ixs.append.len(), 1);
+    assert_eq!(ixs.into_ixs().len(), 3);
+}
+
+#[tokio::test]
+async fn no_wrap_without_wsol() {
+    let (_bc, s) = jito_marinade_basic_amm().await;
+    let params = swap_params(
+        jitosol::ID,
+        msol::ID,
+        Pubkey::new_unique(),
+        &JUPITER_PROGRAM_ID,
+    );
+
+    let ixs = s
+        .swap_ixs_with_wsol_wrap(&params, SwapMode::ExactIn, false)
+        .unwrap();
+    assert!(ixs.prepend.is_empty());
+    assert!(ixs.append.is_empty());
+    assert_eq!(ixs.swap, s.swap_ix(&params, SwapMode::ExactIn).unwrap());
+}
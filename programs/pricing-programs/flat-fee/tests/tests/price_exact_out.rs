@@ -1 +1,44 @@
+use flat_fee_lib::calc::{
+    calculate_price_exact_in, calculate_price_exact_out, CalculatePriceExactInArgs,
+    CalculatePriceExactOutArgs,
+};
 
+/// (input_fee_bps, output_fee_bps)
+const FEE_BPS: [(i16, i16); 4] = [(0, 0), (4, 4), (-2, 10), (25, 100)];
+
+const OUT_SOL_VALUES: [u64; 4] = [1, 999, 1_000_000_000, u32::MAX as u64];
+
+#[test]
+fn exact_out_inverts_exact_in_at_boundary() {
+    for (input_fee_bps, output_fee_bps) in FEE_BPS {
+        let exact_in = |in_sol_value: u64| {
+            calculate_price_exact_in(CalculatePriceExactInArgs {
+                input_fee_bps,
+                output_fee_bps,
+                in_sol_value,
+            })
+            .unwrap()
+        };
+        for out_sol_value in OUT_SOL_VALUES {
+            let in_sol_value = calculate_price_exact_out(CalculatePriceExactOutArgs {
+                input_fee_bps,
+                output_fee_bps,
+                out_sol_value,
+            })
+            .unwrap();
+            // the largest input that prices to exactly out_sol_value
+            assert_eq!(exact_in(in_sol_value), out_sol_value);
+            assert!(exact_in(in_sol_value + 1) > out_sol_value);
+        }
+    }
+}
+
+#[test]
+fn exact_out_rejects_fees_above_100_percent() {
+    assert!(calculate_price_exact_out(CalculatePriceExactOutArgs {
+        input_fee_bps: 5_000,
+        output_fee_bps: 5_001,
+        out_sol_value: 1_000_000_000,
+    })
+    .is_err());
+}
@@ -1 +1,14 @@
+use flat_fee_lib::account_resolvers::PriceLpTokensToMintFreeArgs;
+use solana_program::pubkey::Pubkey;
 
+#[test]
+fn price_lp_tokens_to_mint_resolves_input_mint_only() {
+    let input_lst_mint = Pubkey::new_unique();
+    let keys = PriceLpTokensToMintFreeArgs { input_lst_mint }.resolve();
+    assert_eq!(keys.input_lst_mint, input_lst_mint);
+
+    let [meta] = PriceLpTokensToMintFreeArgs { input_lst_mint }.resolve_to_account_metas();
+    assert_eq!(meta.pubkey, input_lst_mint);
+    assert!(!meta.is_signer);
+    assert!(!meta.is_writable);
+}
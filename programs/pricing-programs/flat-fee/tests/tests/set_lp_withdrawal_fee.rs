@@ -3,11 +3,12 @@ use flat_fee_interface::{
     SetLpWithdrawalFeeKeys,
 };
 use flat_fee_lib::{
-    account_resolvers::SetLpWithdrawalFeeFreeArgs, program::STATE_ID, utils::try_program_state,
+    account_resolvers::SetLpWithdrawalFeeFreeArgs, fee_bound::verify_unsigned_fee_bps_bound,
+    program::STATE_ID, utils::try_program_state,
 };
-use flat_fee_test_utils::FlatFeePricingProgramTestBanksClient;
-use sanctum_solana_test_utils::{assert_custom_err, assert_program_error};
-use solana_program::program_error::ProgramError;
+use flat_fee_test_utils::{FlatFeePricingProgramTestBanksClient, MockProgramState};
+use sanctum_solana_test_utils::{assert_custom_err, assert_program_error, IntoAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 use solana_readonly_account::sdk::KeyedAccount;
 use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
 
@@ -134,3 +135,44 @@ async fn set_lp_withdrawal_fee_fail_unauthorized() {
 
     assert_ne!(state.lp_withdrawal_fee_bps, NEW_LP_WITHDRAWAL_FEE_BPS);
 }
+
+#[test]
+fn set_lp_withdrawal_fee_resolves_manager_from_state() {
+    let manager = Pubkey::new_unique();
+    let state_acc = MockProgramState(ProgramState {
+        manager,
+        lp_withdrawal_fee_bps: Default::default(),
+    })
+    .into_account();
+
+    let SetLpWithdrawalFeeKeys {
+        manager: resolved_manager,
+        state,
+    } = SetLpWithdrawalFeeFreeArgs {
+        state_acc: KeyedAccount {
+            pubkey: STATE_ID,
+            account: state_acc.clone(),
+        },
+    }
+    .resolve()
+    .unwrap();
+    assert_eq!(resolved_manager, manager);
+    assert_eq!(state, STATE_ID);
+
+    assert!(matches!(
+        SetLpWithdrawalFeeFreeArgs {
+            state_acc: KeyedAccount {
+                pubkey: Pubkey::new_unique(),
+                account: state_acc,
+            },
+        }
+        .resolve(),
+        Err(FlatFeeError::IncorrectProgramState)
+    ));
+
+    verify_unsigned_fee_bps_bound(10_000).unwrap();
+    assert!(matches!(
+        verify_unsigned_fee_bps_bound(10_001),
+        Err(FlatFeeError::UnsignedFeeOutOfBound)
+    ));
+}
@@ -1,11 +1,16 @@
-use s_controller_interface::{disable_pool_ix, SControllerError};
-use s_controller_lib::DisablePoolFreeArgs;
+use s_controller_interface::{disable_pool_ix, PoolState, SControllerError};
+use s_controller_lib::{
+    program::{DISABLE_POOL_AUTHORITY_LIST_ID, POOL_STATE_ID},
+    DisablePoolByAdminFreeArgs, DisablePoolFreeArgs,
+};
 use s_controller_test_utils::{
-    assert_pool_disabled, assert_pool_enabled, DisablePoolAuthorityListProgramTest,
+    assert_pool_disabled, assert_pool_enabled, DisablePoolAuthorityListProgramTest, MockPoolState,
     PoolStateProgramTest, DEFAULT_POOL_STATE,
 };
-use sanctum_solana_test_utils::assert_custom_err;
+use sanctum_solana_test_utils::{assert_custom_err, IntoAccount};
+use solana_program::pubkey::Pubkey;
 use solana_program_test::ProgramTest;
+use solana_readonly_account::sdk::KeyedAccount;
 use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
 
 use crate::common::*;
@@ -66,3 +71,38 @@ async fn reject_disable_pool() {
         assert_pool_enabled(&mut banks_client).await;
     }
 }
+
+#[test]
+fn disable_pool_by_admin_resolves_admin_signer() {
+    let admin = Pubkey::new_unique();
+    let pool_state_acc = MockPoolState(PoolState {
+        admin,
+        ..DEFAULT_POOL_STATE
+    })
+    .into_account();
+
+    let keys = DisablePoolByAdminFreeArgs {
+        pool_state_acc: KeyedAccount {
+            pubkey: POOL_STATE_ID,
+            account: pool_state_acc.clone(),
+        },
+    }
+    .resolve()
+    .unwrap();
+    assert_eq!(keys.signer, admin);
+    assert_eq!(keys.pool_state, POOL_STATE_ID);
+    assert_eq!(
+        keys.disable_pool_authority_list,
+        DISABLE_POOL_AUTHORITY_LIST_ID
+    );
+
+    let err = DisablePoolByAdminFreeArgs {
+        pool_state_acc: KeyedAccount {
+            pubkey: Pubkey::new_unique(),
+            account: pool_state_acc,
+        },
+    }
+    .resolve()
+    .unwrap_err();
+    assert_eq!(err, SControllerError::IncorrectPoolState);
+}
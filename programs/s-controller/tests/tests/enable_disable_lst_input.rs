@@ -1,6 +1,7 @@
+use s_controller_interface::SControllerError;
 use s_controller_lib::{
-    disable_lst_input_ix_by_mint_full, enable_lst_input_ix_by_mint_full,
-    DisableEnableLstInputByMintFreeArgs,
+    disable_lst_input_ix_by_mint_full, enable_lst_input_ix_by_mint_full, try_lst_state_list,
+    try_pool_state, DisableEnableLstInputByMintFreeArgs,
 };
 use s_controller_test_utils::{
     assert_lst_input_disabled, assert_lst_input_enabled, jito_marinade_no_fee_program_test,
@@ -50,3 +51,40 @@ async fn basic_disable_then_enable() {
 
     assert_lst_input_enabled(&mut banks_client, jitosol::ID).await;
 }
+
+#[tokio::test]
+async fn by_mint_resolves_admin_and_rejects_unlisted_mint() {
+    let program_test = jito_marinade_no_fee_program_test(
+        JitoMarinadeProgramTestArgs::default().with_lp_token_mint(Pubkey::new_unique()),
+    )
+    .add_s_program();
+    let (mut banks_client, _payer, _last_blockhash) = program_test.start().await;
+    let pool_state = banks_client.get_pool_state_acc().await;
+    let lst_state_list = banks_client.get_lst_state_list_acc().await;
+    let expected_admin = try_pool_state(&pool_state.data).unwrap().admin;
+    let expected_index = try_lst_state_list(&lst_state_list.data)
+        .unwrap()
+        .iter()
+        .position(|lst_state| lst_state.mint == jitosol::ID)
+        .unwrap();
+
+    let args = DisableEnableLstInputByMintFreeArgs {
+        lst_mint: jitosol::ID,
+        pool_state,
+        lst_state_list,
+    };
+    let (keys, lst_index) = args.resolve_disable().unwrap();
+    assert_eq!(keys.admin, expected_admin);
+    assert_eq!(keys.lst_mint, jitosol::ID);
+    assert_eq!(lst_index, expected_index);
+
+    let unlisted_args = DisableEnableLstInputByMintFreeArgs {
+        lst_mint: Pubkey::new_unique(),
+        ..args
+    };
+    assert_eq!(
+        unlisted_args.resolve_disable().unwrap_err(),
+        SControllerError::InvalidLstIndex
+    );
+    assert!(disable_lst_input_ix_by_mint_full(&unlisted_args).is_err());
+}
@@ -1,9 +1,10 @@
-use s_controller_interface::{enable_pool_ix, PoolState};
+use s_controller_interface::{enable_pool_ix, PoolState, SControllerError};
 use s_controller_lib::{program::POOL_STATE_ID, EnablePoolFreeArgs};
 use s_controller_test_utils::{
     assert_pool_enabled, MockPoolState, PoolStateProgramTest, DEFAULT_POOL_STATE,
 };
 use sanctum_solana_test_utils::{test_fixtures_dir, IntoAccount};
+use solana_program::pubkey::Pubkey;
 use solana_program_test::ProgramTest;
 use solana_readonly_account::sdk::KeyedAccount;
 use solana_sdk::{signature::read_keypair_file, signer::Signer, transaction::Transaction};
@@ -47,3 +48,34 @@ async fn basic_enable_pool() {
         assert_pool_enabled(&mut banks_client).await;
     }
 }
+
+#[test]
+fn enable_pool_resolves_admin_signer() {
+    let admin = Pubkey::new_unique();
+    let pool_state_acc = MockPoolState(PoolState {
+        admin,
+        ..DEFAULT_POOL_STATE
+    })
+    .into_account();
+
+    let keys = EnablePoolFreeArgs {
+        pool_state_acc: KeyedAccount {
+            pubkey: POOL_STATE_ID,
+            account: pool_state_acc.clone(),
+        },
+    }
+    .resolve()
+    .unwrap();
+    assert_eq!(keys.admin, admin);
+    assert_eq!(keys.pool_state, POOL_STATE_ID);
+
+    let err = EnablePoolFreeArgs {
+        pool_state_acc: KeyedAccount {
+            pubkey: Pubkey::new_unique(),
+            account: pool_state_acc,
+        },
+    }
+    .resolve()
+    .unwrap_err();
+    assert_eq!(err, SControllerError::IncorrectPoolState);
+}
@@ -234,6 +234,76 @@ async fn rebalance_basic() {
     assert!(rebalance_record.is_none());
 }
 
+#[tokio::test]
+async fn rebalance_ixs_share_rebalance_record() {
+    let program_test = jito_marinade_no_fee_program_test(JitoMarinadeProgramTestArgs {
+        jitosol_sol_value: 1_000_000_000,
+        msol_sol_value: 1_000_000_000,
+        jitosol_reserves: 1_000_000_000,
+        msol_reserves: 1_000_000_000,
+        jitosol_protocol_fee_accumulator: 0,
+        msol_protocol_fee_accumulator: 0,
+        lp_token_mint: Pubkey::new_unique(),
+        lp_token_supply: 0,
+    })
+    .add_s_program();
+    let (mut banks_client, _payer, _last_blockhash) = program_test.start().await;
+
+    let lst_state_list_acc = banks_client.get_lst_state_list_acc().await;
+    let pool_state_acc = banks_client.get_pool_state_acc().await;
+    let jito_stake_pool_acc = banks_client
+        .get_account_unwrapped(jito_stake_pool::ID)
+        .await;
+
+    let same_mint_err = StartRebalanceByMintsFreeArgs {
+        withdraw_to: Pubkey::new_unique(),
+        lst_state_list: KeyedAccount {
+            pubkey: LST_STATE_LIST_ID,
+            account: lst_state_list_acc.clone(),
+        },
+        pool_state: KeyedAccount {
+            pubkey: POOL_STATE_ID,
+            account: pool_state_acc.clone(),
+        },
+        src_lst_mint: MintWithTokenProgram {
+            pubkey: jitosol::ID,
+            token_program: spl_token::ID,
+        },
+        dst_lst_mint: MintWithTokenProgram {
+            pubkey: jitosol::ID,
+            token_program: spl_token::ID,
+        },
+    }
+    .resolve()
+    .unwrap_err();
+    assert_eq!(same_mint_err, SControllerError::SwapSameLst);
+
+    let [start_rebalance_ix, _donate_ix, end_rebalance_ix] =
+        create_rebalance_donate_ixs(CreateRebalanceDonateIxsArgs {
+            jito_stake_pool_acc,
+            pool_state_acc,
+            lst_state_list_acc,
+            withdraw_jitosol_to_addr: Pubkey::new_unique(),
+            donate_msol_from_addr: Pubkey::new_unique(),
+            donate_msol_authority: Pubkey::new_unique(),
+            jitosol_withdraw_amt: 1,
+            msol_donate_amt: 1,
+            min_starting_src_lst: 0,
+            max_starting_dst_lst: u64::MAX,
+        });
+    for ix in [&start_rebalance_ix, &end_rebalance_ix] {
+        assert!(ix
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == REBALANCE_RECORD_ID && meta.is_writable));
+        // dst LST's sol value calculator program
+        assert!(ix
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == marinade_calculator_lib::program::ID));
+    }
+}
+
 #[tokio::test]
 async fn rebalance_fail_no_end() {
     let mock_auth_kp =
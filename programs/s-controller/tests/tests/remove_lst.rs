@@ -1,7 +1,7 @@
-use s_controller_interface::{remove_lst_ix, RemoveLstIxArgs};
+use s_controller_interface::{remove_lst_ix, RemoveLstIxArgs, SControllerError};
 use s_controller_lib::{
     program::{LST_STATE_LIST_ID, POOL_STATE_ID},
-    try_lst_state_list, FindLstPdaAtaKeys, RemoveLstFreeArgs,
+    try_lst_state_list, FindLstPdaAtaKeys, RemoveLstByMintFreeArgs, RemoveLstFreeArgs,
 };
 use s_controller_test_utils::{
     assert_lst_removed, jito_marinade_no_fee_program_test, JitoMarinadeProgramTestArgs,
@@ -130,6 +130,78 @@ async fn basic_three_clear_1_0_2() {
     exec_verify_remove(&mut banks_client, 0, &payer, last_blockhash, &mock_auth_kp).await;
 }
 
+#[tokio::test]
+async fn by_mint_resolves_index_and_rejects_absent_mint() {
+    let random_lst_states = [0; 3].map(|_| MockLstStateArgs {
+        mint: Pubkey::new_unique(),
+        token_program: spl_token::ID,
+        sol_value_calculator: Pubkey::default(),
+        sol_value: 0,
+        reserves_amt: 0,
+        protocol_fee_accumulator_amt: 0,
+        is_input_disabled: false,
+    });
+
+    let mut program_test = ProgramTest::default()
+        .add_s_program()
+        .add_pool_state(DEFAULT_POOL_STATE)
+        .add_mock_lst_states(&random_lst_states);
+    let absent_mint = Pubkey::new_unique();
+    for mint in random_lst_states
+        .iter()
+        .map(|s| s.mint)
+        .chain(std::iter::once(absent_mint))
+    {
+        program_test = program_test.add_tokenkeg_mint_from_args(
+            mint,
+            MockMintArgs {
+                mint_authority: None,
+                freeze_authority: None,
+                supply: 0,
+                decimals: 9,
+            },
+        );
+    }
+
+    let (mut banks_client, payer, _last_blockhash) = program_test.start().await;
+    let pool_state_acc = banks_client.get_pool_state_acc().await;
+    let lst_state_list_acc = banks_client.get_lst_state_list_acc().await;
+
+    for mint in random_lst_states
+        .iter()
+        .map(|s| s.mint)
+        .chain(std::iter::once(absent_mint))
+    {
+        let mint_acc = banks_client.get_account(mint).await.unwrap().unwrap();
+        let res = RemoveLstByMintFreeArgs {
+            refund_rent_to: payer.pubkey(),
+            pool_state: KeyedAccount {
+                pubkey: POOL_STATE_ID,
+                account: pool_state_acc.clone(),
+            },
+            lst_state_list: KeyedAccount {
+                pubkey: LST_STATE_LIST_ID,
+                account: lst_state_list_acc.clone(),
+            },
+            lst_mint: KeyedAccount {
+                pubkey: mint,
+                account: mint_acc,
+            },
+        }
+        .resolve();
+        match random_lst_states.iter().position(|s| s.mint == mint) {
+            Some(expected_index) => {
+                let (keys, RemoveLstIxArgs { lst_index }) = res.unwrap();
+                assert_eq!(usize::try_from(lst_index).unwrap(), expected_index);
+                assert_eq!(keys.lst_mint, mint);
+                assert_eq!(keys.lst_token_program, spl_token::ID);
+                assert_eq!(keys.admin, DEFAULT_POOL_STATE.admin);
+            }
+            None => assert_eq!(res.unwrap_err(), SControllerError::InvalidLstIndex),
+        }
+    }
+}
+
 async fn exec_verify_remove(
     banks_client: &mut BanksClient,
     lst_index: usize,
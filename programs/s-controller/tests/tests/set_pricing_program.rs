@@ -1,12 +1,15 @@
-use s_controller_interface::{set_pricing_program_ix, SControllerError};
+use s_controller_interface::{set_pricing_program_ix, PoolState, SControllerError};
 use s_controller_lib::{
     program::POOL_STATE_ID, try_pool_state, SetPricingProgramFreeArgs, DEFAULT_PRICING_PROGRAM,
 };
 
 use s_controller_test_utils::{
-    assert_pricing_prog_set, PoolStateBanksClient, PoolStateProgramTest, DEFAULT_POOL_STATE,
+    assert_pricing_prog_set, MockPoolState, PoolStateBanksClient, PoolStateProgramTest,
+    DEFAULT_POOL_STATE,
+};
+use sanctum_solana_test_utils::{
+    assert_custom_err, assert_program_error, test_fixtures_dir, IntoAccount,
 };
-use sanctum_solana_test_utils::{assert_custom_err, assert_program_error, test_fixtures_dir};
 use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 use solana_program_test::{processor, ProgramTest};
 use solana_readonly_account::sdk::KeyedAccount;
@@ -35,6 +38,42 @@ fn no_fee_program_test() -> (ProgramTest, Keypair) {
     (program_test, mock_auth_kp)
 }
 
+#[test]
+fn resolve_reads_admin_from_pool_state() {
+    let admin = Pubkey::new_unique();
+    let account = MockPoolState(PoolState {
+        admin,
+        ..DEFAULT_POOL_STATE
+    })
+    .into_account();
+
+    let keys = SetPricingProgramFreeArgs {
+        new_pricing_program: no_fee_pricing_program::ID,
+        pool_state_acc: KeyedAccount {
+            pubkey: POOL_STATE_ID,
+            account: account.clone(),
+        },
+    }
+    .resolve()
+    .unwrap();
+    assert_eq!(keys.admin, admin);
+    assert_eq!(keys.new_pricing_program, no_fee_pricing_program::ID);
+    assert_eq!(keys.pool_state, POOL_STATE_ID);
+
+    let wrong_pool_state = SetPricingProgramFreeArgs {
+        new_pricing_program: no_fee_pricing_program::ID,
+        pool_state_acc: KeyedAccount {
+            pubkey: Pubkey::new_unique(),
+            account,
+        },
+    }
+    .resolve();
+    assert!(matches!(
+        wrong_pool_state,
+        Err(SControllerError::IncorrectPoolState)
+    ));
+}
+
 #[tokio::test]
 async fn basic_success() {
     let (program_test, mock_auth_kp) = no_fee_program_test();
@@ -1,7 +1,9 @@
 use s_controller_interface::{
-    set_protocol_fee_ix, PoolState, SetProtocolFeeIxArgs, SetProtocolFeeKeys,
+    set_protocol_fee_ix, PoolState, SControllerError, SetProtocolFeeIxArgs, SetProtocolFeeKeys,
+};
+use s_controller_lib::{
+    program::POOL_STATE_ID, set_protocol_fee_ix_full, try_pool_state, SetProtocolFeeFreeArgs,
 };
-use s_controller_lib::{program::POOL_STATE_ID, try_pool_state, SetProtocolFeeFreeArgs};
 use s_controller_test_utils::{
     MockPoolState, PoolStateBanksClient, PoolStateProgramTest, DEFAULT_POOL_STATE,
 };
@@ -75,6 +77,66 @@ async fn unauthorized_signer() {
     assert_program_error(err, ProgramError::InvalidArgument);
 }
 
+#[tokio::test]
+async fn ix_full_admin_set_trading() {
+    let mock_auth_kp =
+        read_keypair_file(test_fixtures_dir().join("s-controller-test-initial-authority-key.json"))
+            .unwrap();
+
+    let old_pool_state = DEFAULT_POOL_STATE;
+    let program_test = ProgramTest::default()
+        .add_s_program()
+        .add_pool_state(old_pool_state);
+    let (mut banks_client, payer, last_blockhash) = program_test.start().await;
+
+    let args = SetProtocolFeeIxArgs {
+        new_trading_protocol_fee_bps: Some(10_000),
+        new_lp_protocol_fee_bps: None,
+    };
+    let ix = set_protocol_fee_ix_full(
+        SetProtocolFeeFreeArgs {
+            pool_state: KeyedAccount {
+                pubkey: POOL_STATE_ID,
+                account: MockPoolState(old_pool_state).into_account(),
+            },
+        },
+        args.clone(),
+    )
+    .unwrap();
+
+    let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+    tx.sign(&[&payer, &mock_auth_kp], last_blockhash);
+    banks_client.process_transaction(tx).await.unwrap();
+
+    verify_set_correct(&mut banks_client, old_pool_state, args).await;
+}
+
+#[test]
+fn ix_full_rejects_fee_above_100_percent() {
+    for args in [
+        SetProtocolFeeIxArgs {
+            new_trading_protocol_fee_bps: Some(10_001),
+            new_lp_protocol_fee_bps: None,
+        },
+        SetProtocolFeeIxArgs {
+            new_trading_protocol_fee_bps: Some(1),
+            new_lp_protocol_fee_bps: Some(u16::MAX),
+        },
+    ] {
+        let err = set_protocol_fee_ix_full(
+            SetProtocolFeeFreeArgs {
+                pool_state: KeyedAccount {
+                    pubkey: POOL_STATE_ID,
+                    account: MockPoolState(DEFAULT_POOL_STATE).into_account(),
+                },
+            },
+            args,
+        )
+        .unwrap_err();
+        assert_eq!(err, SControllerError::FeeTooHigh.into());
+    }
+}
+
 // TODO: tests for setting one-by-one to make sure Option works
 
 async fn verify_set_correct(
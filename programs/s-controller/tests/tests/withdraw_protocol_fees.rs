@@ -1,19 +1,24 @@
 use marinade_keys::msol;
-use s_controller_interface::{withdraw_protocol_fees_ix, WithdrawProtocolFeesIxArgs};
+use s_controller_interface::{
+    withdraw_protocol_fees_ix, SControllerError, WithdrawProtocolFeesIxArgs,
+};
 use s_controller_lib::{
-    find_protocol_fee_accumulator_address, program::POOL_STATE_ID, FindLstPdaAtaKeys,
-    WithdrawProtocolFeesFreeArgs,
+    find_protocol_fee_accumulator_address,
+    program::{LST_STATE_LIST_ID, POOL_STATE_ID},
+    try_pool_state, withdraw_protocol_fees_ix_full, FindLstPdaAtaKeys,
+    WithdrawProtocolFeesByListedMintFreeArgs, WithdrawProtocolFeesFreeArgs,
 };
 use s_controller_test_utils::{
-    jito_marinade_no_fee_program_test, JitoMarinadeProgramTestArgs, PoolStateBanksClient,
+    jito_marinade_no_fee_program_test, JitoMarinadeProgramTestArgs, LstStateListBanksClient,
+    PoolStateBanksClient,
 };
 use sanctum_solana_test_utils::{
     test_fixtures_dir,
     token::{tokenkeg::TokenkegProgramTest, MockTokenAccountArgs},
     ExtendedBanksClient,
 };
-use sanctum_token_lib::{token_account_balance, token_account_mint};
-use solana_program::pubkey::Pubkey;
+use sanctum_token_lib::{token_account_balance, token_account_mint, MintWithTokenProgram};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 use solana_program_test::*;
 use solana_readonly_account::sdk::KeyedAccount;
 use solana_sdk::{
@@ -118,3 +123,112 @@ async fn basic_withdraw_protocol_fees() {
         new_protocol_fee_accumulator_balance + MSOL_FEES_TO_WITHDRAW
     );
 }
+
+#[tokio::test]
+async fn withdraw_all_protocol_fees_by_listed_mint() {
+    let mock_auth_kp =
+        read_keypair_file(test_fixtures_dir().join("s-controller-test-initial-authority-key.json"))
+            .unwrap();
+    const MSOL_ACCUMULATED_FEES: u64 = 10_000_000_000;
+
+    let auth_msol_acc_addr = Pubkey::new_unique();
+
+    let program_test = jito_marinade_no_fee_program_test(JitoMarinadeProgramTestArgs {
+        jitosol_sol_value: 0,
+        msol_sol_value: 0,
+        jitosol_reserves: 0,
+        msol_reserves: 0,
+        jitosol_protocol_fee_accumulator: 0,
+        msol_protocol_fee_accumulator: MSOL_ACCUMULATED_FEES,
+        lp_token_mint: Pubkey::new_unique(),
+        lp_token_supply: 0,
+    })
+    .add_tokenkeg_account_from_args(
+        auth_msol_acc_addr,
+        MockTokenAccountArgs {
+            mint: msol::ID,
+            authority: mock_auth_kp.pubkey(),
+            amount: 0,
+        },
+    )
+    .add_s_program();
+
+    let (mut banks_client, payer, last_blockhash) = program_test.start().await;
+
+    let pool_state_acc = banks_client.get_pool_state_acc().await;
+    let lst_state_list_acc = banks_client.get_lst_state_list_acc().await;
+    let free_args = |lst_mint| WithdrawProtocolFeesByListedMintFreeArgs {
+        pool_state: KeyedAccount {
+            pubkey: POOL_STATE_ID,
+            account: pool_state_acc.clone(),
+        },
+        lst_state_list: KeyedAccount {
+            pubkey: LST_STATE_LIST_ID,
+            account: lst_state_list_acc.clone(),
+        },
+        lst_mint: MintWithTokenProgram {
+            pubkey: lst_mint,
+            token_program: spl_token::ID,
+        },
+        withdraw_to: auth_msol_acc_addr,
+    };
+
+    assert_eq!(
+        free_args(Pubkey::new_unique()).resolve().unwrap_err(),
+        ProgramError::from(SControllerError::InvalidLstIndex)
+    );
+
+    let keys = free_args(msol::ID).resolve().unwrap();
+    let (expected_accumulator, _bump) = find_protocol_fee_accumulator_address(FindLstPdaAtaKeys {
+        lst_mint: msol::ID,
+        token_program: spl_token::ID,
+    });
+    assert_eq!(keys.protocol_fee_accumulator, expected_accumulator);
+    assert_eq!(
+        keys.protocol_fee_beneficiary,
+        try_pool_state(&pool_state_acc.data)
+            .unwrap()
+            .protocol_fee_beneficiary
+    );
+
+    let protocol_fee_accumulator_acc = banks_client
+        .get_account_unwrapped(expected_accumulator)
+        .await;
+    assert_eq!(
+        withdraw_protocol_fees_ix_full(
+            keys,
+            None,
+            KeyedAccount {
+                pubkey: Pubkey::new_unique(),
+                account: protocol_fee_accumulator_acc.clone(),
+            },
+        )
+        .unwrap_err(),
+        ProgramError::InvalidArgument
+    );
+    let ix = withdraw_protocol_fees_ix_full(
+        keys,
+        None,
+        KeyedAccount {
+            pubkey: expected_accumulator,
+            account: protocol_fee_accumulator_acc,
+        },
+    )
+    .unwrap();
+    let mut tx = Transaction::new_with_payer(&[ix], Some(&payer.pubkey()));
+    tx.sign(&[&payer, &mock_auth_kp], last_blockhash);
+    banks_client.process_transaction(tx).await.unwrap();
+
+    let msol_account = banks_client.get_account_unwrapped(auth_msol_acc_addr).await;
+    assert_eq!(
+        token_account_balance(msol_account).unwrap(),
+        MSOL_ACCUMULATED_FEES
+    );
+    let protocol_fee_accumulator_acc = banks_client
+        .get_account_unwrapped(expected_accumulator)
+        .await;
+    assert_eq!(
+        token_account_balance(protocol_fee_accumulator_acc).unwrap(),
+        0
+    );
+}